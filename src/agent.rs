@@ -0,0 +1,110 @@
+use crate::betting::{Action, LegalActions};
+use crate::io::{InputProvider, OutputSink, StdinInput, TerminalOutput};
+use crate::player::Player;
+use crate::strategy::{DecisionContext, Difficulty};
+use rand::RngCore;
+
+pub struct PlayerView<'a> { // Ce que voit le joueur dont c'est le tour, et rien de plus
+    pub name: &'a str,
+    pub stage: &'a str,
+    pub context: DecisionContext<'a>,
+    pub legal: &'a LegalActions,
+    pub advice: &'a [String], // Équité et conseils préparés pour les joueurs humains (vide pour les IA)
+    pub shared_screen: bool, // Plusieurs humains sont assis à la table
+}
+
+pub trait PlayerAgent { // Prend les décisions d'un joueur: humain au clavier ou à distance, script, IA
+    fn decide(&mut self, view: &PlayerView, rng: &mut dyn RngCore) -> Option<Action>; // Une action illégale est ramenée à l'action légale la plus proche; None quand l'agent ne peut plus jouer (fin de son entrée)
+}
+
+pub fn default_agent(player: &Player) -> Box<dyn PlayerAgent> { // Le terminal pour un humain, la stratégie de son niveau pour une IA
+    if player.is_human {
+        Box::new(HumanAgent::terminal())
+    } else {
+        Box::new(BotAgent(player.difficulty.unwrap_or_default()))
+    }
+}
+
+pub struct BotAgent(pub Difficulty);
+
+impl PlayerAgent for BotAgent {
+    fn decide(&mut self, view: &PlayerView, rng: &mut dyn RngCore) -> Option<Action> {
+        Some(self.0.strategy().decide(&view.context, rng))
+    }
+}
+
+pub struct HumanAgent { // Joueur qui lit sa situation et tape ses commandes (f, k, c, b 20, r 40, allin)
+    input: Box<dyn InputProvider>,
+    output: Box<dyn OutputSink>,
+    hot_seat: bool, // Terminal partagé: l'écran est effacé et le clavier passé entre les humains
+}
+
+impl HumanAgent {
+    pub fn new(input: Box<dyn InputProvider>, output: Box<dyn OutputSink>) -> Self { // Joueur distant ou scripté, seul devant sa sortie
+        Self { input, output, hot_seat: false }
+    }
+
+    pub fn terminal() -> Self {
+        Self { input: Box::new(StdinInput), output: Box::new(TerminalOutput), hot_seat: true }
+    }
+
+    fn show_view(&mut self, view: &PlayerView) -> Option<()> { // Montre ses cartes au joueur qui doit agir, et à lui seul; None en fin d'entrée
+        if self.hot_seat && view.shared_screen {
+            self.output.clear();
+            self.output.line(&format!("| Passez le clavier à {} puis appuyez sur Entrée.", view.name));
+            self.input.read_line()?;
+            self.output.clear();
+        }
+        let context = &view.context;
+        self.output.line(&format!("| {}, vos cartes: {} et {}", view.name, context.hand[0], context.hand[1]));
+        self.output.line(&format!("| Cartes communes: [{}] | Pot: {} jetons", context.community.iter().map(|c| c.to_string()).collect::<Vec<_>>().join(", "), context.pot));
+        for advice in view.advice {
+            self.output.line(advice);
+        }
+        self.output.line(&format!("| {}, vous avez {} jetons. Mise requise: {}.", view.name, context.chips, context.to_call));
+        self.output.line(&format!("| Actions: {}", action_menu(view.legal)));
+        Some(())
+    }
+}
+
+impl PlayerAgent for HumanAgent {
+    fn decide(&mut self, view: &PlayerView, _rng: &mut dyn RngCore) -> Option<Action> {
+        self.show_view(view)?;
+        let action = loop {
+            let input = self.input.read_line()?; // Fin de l'entrée: la partie s'arrête à la fin de la main
+            match input.parse::<Action>() {
+                Ok(action) => match view.legal.validate(action) {
+                    Ok(_) => break action,
+                    Err(reason) => self.output.line(&format!("| Action refusée: {}.", reason)),
+                },
+                Err(reason) => self.output.line(&format!("| Entrée invalide: {}.", reason)),
+            }
+        };
+        if self.hot_seat && view.shared_screen { // Efface les cartes avant de rendre le terminal aux autres
+            self.output.clear();
+        }
+        Some(action)
+    }
+}
+
+pub fn action_menu(legal: &LegalActions) -> String { // Commandes permises, par exemple "f (se coucher), c (suivre 10), r <total> (...)"
+    let mut commands = Vec::new();
+    if legal.to_call == 0 {
+        commands.push("k (passer)".to_string());
+    } else {
+        commands.push("f (se coucher)".to_string());
+        commands.push(format!("c (suivre {})", legal.to_call.min(legal.stack)));
+    }
+    if let Ok(bounds) = &legal.raise {
+        let (command, label) = if legal.opening { ("b", "miser") } else { ("r", "relancer") };
+        if bounds.min_total == bounds.max_total {
+            commands.push(format!("{} {} ({} à {})", command, bounds.min_total, label, bounds.min_total));
+        } else {
+            commands.push(format!("{} <total> ({} à un total entre {} et {})", command, label, bounds.min_total, bounds.max_total));
+        }
+    }
+    if legal.validate(Action::AllIn).is_ok() {
+        commands.push(format!("allin (tapis, mise totale de {})", legal.current_bet + legal.stack));
+    }
+    commands.join(", ")
+}
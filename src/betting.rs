@@ -0,0 +1,376 @@
+use crate::player::Player;
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[allow(clippy::enum_variant_names)] // Noms usuels des structures de mise
+pub enum BettingStructure { // Limites de mise d'une partie
+    #[default]
+    NoLimit,
+    PotLimit,
+    FixedLimit { small_bet: u32, big_bet: u32, raise_cap: u32 }, // raise_cap: nombre maximal de mises par rue (mise initiale comprise)
+}
+
+impl BettingStructure {
+    pub fn fixed_limit(big_blind: u32) -> Self { // Limite classique: petite mise = grosse blind, grosse mise au turn et à la river, quatre mises par rue
+        BettingStructure::FixedLimit { small_bet: big_blind, big_bet: 2 * big_blind, raise_cap: 4 }
+    }
+
+    pub fn bet_size(&self, stage: &str, big_blind: u32) -> u32 { // Mise minimale de la rue (mise fixe en limite)
+        match self {
+            BettingStructure::FixedLimit { small_bet, big_bet, .. } => match stage {
+                "Pré-flop" | "Flop" => *small_bet,
+                _ => *big_bet,
+            },
+            _ => big_blind,
+        }
+    }
+}
+
+impl fmt::Display for BettingStructure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BettingStructure::NoLimit => write!(f, "No-limit"),
+            BettingStructure::PotLimit => write!(f, "Pot-limit"),
+            BettingStructure::FixedLimit { small_bet, big_bet, raise_cap } => write!(f, "Limite {}/{} ({} mises maximum par rue)", small_bet, big_bet, raise_cap),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BettingRound { // État des mises d'une rue
+    pub structure: BettingStructure,
+    pub current_bet: u32, // Mise la plus haute de la rue
+    pub min_raise: u32, // Taille de la dernière relance complète (la mise de la rue au départ)
+    bets: u32, // Nombre de mises et relances complètes de la rue, blinds comprises
+    full_raise_level: u32, // Mise totale atteinte par la dernière relance complète
+    acted_at: Vec<Option<u32>>, // Mise la plus haute au moment où chaque joueur a agi pour la dernière fois
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BetError { // Raisons pour lesquelles une mise est refusée
+    NotEnoughChips { available: u32 },
+    FoldWhenCheckFree,
+    CannotCheck { to_call: u32 },
+    BelowMinRaise { min_total: u32, min_raise: u32 },
+    AboveMaxRaise { max_total: u32 },
+    RaiseCapReached { to_call: u32 },
+    ActionNotReopened { to_call: u32 },
+}
+
+impl fmt::Display for BetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BetError::NotEnoughChips { available } => write!(f, "vous n'avez pas assez de jetons (maximum possible: {})", available),
+            BetError::FoldWhenCheckFree => write!(f, "personne n'a misé, vous pouvez passer gratuitement au lieu de vous coucher"),
+            BetError::CannotCheck { to_call } => write!(f, "impossible de passer, il faut {} pour suivre", to_call),
+            BetError::BelowMinRaise { min_total, min_raise } => write!(f, "relance trop petite: la relance minimale est de {} (mise totale d'au moins {})", min_raise, min_total),
+            BetError::AboveMaxRaise { max_total } => write!(f, "relance trop grande: la mise totale maximale est de {}", max_total),
+            BetError::RaiseCapReached { to_call } => write!(f, "nombre maximal de relances atteint pour cette rue, vous pouvez seulement suivre ({}) ou vous coucher", to_call),
+            BetError::ActionNotReopened { to_call } => write!(f, "la relance à tapis précédente était incomplète, vous pouvez seulement suivre ({}) ou vous coucher", to_call),
+        }
+    }
+}
+
+impl std::error::Error for BetError {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action { // Décision d'un joueur; les montants de mise et de relance sont des mises totales pour la rue
+    Fold,
+    Check,
+    Call,
+    Bet(u32),
+    Raise(u32),
+    AllIn,
+}
+
+impl fmt::Display for Action {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Action::Fold => write!(f, "se couche"),
+            Action::Check => write!(f, "passe"),
+            Action::Call => write!(f, "suit"),
+            Action::Bet(total) => write!(f, "mise {}", total),
+            Action::Raise(total) => write!(f, "relance à {}", total),
+            Action::AllIn => write!(f, "fait tapis"),
+        }
+    }
+}
+
+impl FromStr for Action { // Commandes du terminal: f, k, c, b 20, r 40, allin
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut words = s.split_whitespace();
+        let command = words.next().unwrap_or("").to_lowercase();
+        let amount = words.next().map(|word| word.parse::<u32>().map_err(|_| format!("montant invalide: '{}'", word)));
+        if words.next().is_some() {
+            return Err("trop d'arguments".to_string());
+        }
+        let amount_for = |command: &str| match amount.clone() {
+            Some(amount) => amount,
+            None => Err(format!("indiquez la mise totale, par exemple '{} 40'", command)),
+        };
+        match command.as_str() {
+            "f" | "fold" => Ok(Action::Fold),
+            "k" | "x" | "check" => Ok(Action::Check),
+            "c" | "call" => Ok(Action::Call),
+            "b" | "bet" => Ok(Action::Bet(amount_for(&command)?)),
+            "r" | "raise" => Ok(Action::Raise(amount_for(&command)?)),
+            "a" | "allin" | "all-in" => Ok(Action::AllIn),
+            "" => Err("entrez une action".to_string()),
+            other => Err(format!("action inconnue: '{}'", other)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RaiseBounds { // Mises totales minimale et maximale d'une mise ou d'une relance
+    pub min_total: u32,
+    pub max_total: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LegalActions { // Réponse de `legal_actions`: ce qu'un joueur peut faire et avec quels montants
+    pub current_bet: u32, // Mise déjà placée par le joueur dans la rue
+    pub to_call: u32, // Jetons manquants pour égaler (0: passer est gratuit)
+    pub stack: u32,
+    pub opening: bool, // Personne n'a encore misé dans la rue
+    pub min_raise: u32,
+    pub raise: Result<RaiseBounds, BetError>, // Bornes de relance, ou la raison pour laquelle relancer est interdit
+}
+
+impl LegalActions {
+    pub fn actions(&self) -> Vec<Action> { // Liste des actions possibles (montant minimal pour miser ou relancer)
+        let mut actions = Vec::new();
+        if self.to_call == 0 {
+            actions.push(Action::Check);
+        } else {
+            actions.push(Action::Fold);
+            actions.push(Action::Call);
+        }
+        if let Ok(bounds) = &self.raise {
+            let all_in_total = self.current_bet + self.stack;
+            if bounds.min_total < all_in_total {
+                actions.push(if self.opening { Action::Bet(bounds.min_total) } else { Action::Raise(bounds.min_total) });
+            }
+        }
+        if self.validate(Action::AllIn).is_ok() {
+            actions.push(Action::AllIn);
+        }
+        actions
+    }
+
+    pub fn validate(&self, action: Action) -> Result<u32, BetError> { // Renvoie les jetons à ajouter, ou la raison du refus
+        let all_in_total = self.current_bet + self.stack;
+        match action {
+            Action::Fold if self.to_call == 0 => Err(BetError::FoldWhenCheckFree),
+            Action::Fold => Ok(0),
+            Action::Check if self.to_call > 0 => Err(BetError::CannotCheck { to_call: self.to_call }),
+            Action::Check => Ok(0),
+            Action::Call => Ok(self.to_call.min(self.stack)), // Un joueur trop court suit à tapis
+            Action::AllIn if self.stack <= self.to_call => Ok(self.stack),
+            Action::AllIn => {
+                let bounds = self.raise.clone()?;
+                if all_in_total > bounds.max_total {
+                    return Err(BetError::AboveMaxRaise { max_total: bounds.max_total });
+                }
+                Ok(self.stack)
+            }
+            Action::Bet(total) | Action::Raise(total) => {
+                let bounds = self.raise.clone()?;
+                if total > all_in_total {
+                    return Err(BetError::NotEnoughChips { available: self.stack });
+                }
+                if total > bounds.max_total {
+                    return Err(BetError::AboveMaxRaise { max_total: bounds.max_total });
+                }
+                if total < bounds.min_total { // Une relance à tapis incomplète reste permise, via le plafond du tapis
+                    return Err(BetError::BelowMinRaise { min_total: bounds.min_total, min_raise: self.min_raise });
+                }
+                Ok(total - self.current_bet)
+            }
+        }
+    }
+
+    pub fn nearest(&self, action: Action) -> Action { // Ramène une décision d'IA à l'action légale la plus proche
+        if self.validate(action).is_ok() {
+            return action;
+        }
+        let passive = if self.to_call == 0 { Action::Check } else { Action::Call };
+        match (action, &self.raise) {
+            (Action::Fold, _) | (Action::Check, _) if self.to_call == 0 => Action::Check,
+            (Action::Fold, _) | (Action::Check, _) => Action::Fold,
+            (Action::Bet(total), Ok(bounds)) | (Action::Raise(total), Ok(bounds)) => {
+                let total = total.clamp(bounds.min_total, bounds.max_total);
+                if self.opening { Action::Bet(total) } else { Action::Raise(total) }
+            }
+            (Action::AllIn, Ok(bounds)) => Action::Raise(bounds.max_total),
+            _ => passive,
+        }
+    }
+}
+
+impl BettingRound {
+    pub fn new(num_players: usize, current_bet: u32, bet_size: u32, structure: BettingStructure) -> Self { // Nouvelle rue: la mise à égaler et la relance minimale de départ
+        Self {
+            structure,
+            current_bet,
+            min_raise: bet_size,
+            bets: u32::from(current_bet > 0),
+            full_raise_level: current_bet,
+            acted_at: vec![None; num_players],
+        }
+    }
+
+    pub fn post_straddle(&mut self, total: u32) { // Le straddle est une mise à l'aveugle: il fixe la mise à égaler sans clore la parole du straddler
+        self.bets += 1;
+        if !matches!(self.structure, BettingStructure::FixedLimit { .. }) { // En no-limit et pot-limit, la relance minimale devient le straddle
+            self.min_raise = self.min_raise.max(total);
+        }
+        self.full_raise_level = total;
+        self.current_bet = total;
+    }
+
+    pub fn to_call(&self, player: &Player) -> u32 {
+        self.current_bet.saturating_sub(player.current_bet)
+    }
+
+    pub fn min_raise_to(&self) -> u32 { // Mise totale minimale pour une relance complète
+        self.current_bet + self.min_raise
+    }
+
+    pub fn max_raise_to(&self, player: &Player, pot: u32) -> u32 { // Mise totale maximale selon la structure (avant la limite du tapis)
+        match self.structure {
+            BettingStructure::NoLimit => player.current_bet + player.chips,
+            BettingStructure::PotLimit => self.current_bet + pot + self.to_call(player), // Suivre puis relancer du montant du pot après le call
+            BettingStructure::FixedLimit { .. } => self.min_raise_to(),
+        }
+    }
+
+    fn raise_cap_reached(&self) -> bool {
+        match self.structure {
+            BettingStructure::FixedLimit { raise_cap, .. } => self.bets >= raise_cap,
+            _ => false,
+        }
+    }
+
+    pub fn can_raise(&self, player_index: usize) -> bool { // Une relance n'est permise que si une relance complète a eu lieu depuis la dernière action du joueur
+        !self.raise_cap_reached() && self.acted_at[player_index].is_none_or(|level| self.full_raise_level > level)
+    }
+
+    pub fn legal_actions(&self, player_index: usize, player: &Player, pot: u32) -> LegalActions { // Actions permises au joueur et bornes de relance
+        let to_call = self.to_call(player);
+        let all_in_total = player.current_bet + player.chips;
+        let raise = if all_in_total <= self.current_bet {
+            Err(BetError::NotEnoughChips { available: player.chips })
+        } else if self.raise_cap_reached() {
+            Err(BetError::RaiseCapReached { to_call })
+        } else if !self.can_raise(player_index) {
+            Err(BetError::ActionNotReopened { to_call })
+        } else {
+            Ok(RaiseBounds {
+                min_total: self.min_raise_to().min(all_in_total),
+                max_total: self.max_raise_to(player, pot).min(all_in_total),
+            })
+        };
+        LegalActions {
+            current_bet: player.current_bet,
+            to_call,
+            stack: player.chips,
+            opening: self.current_bet == 0,
+            min_raise: self.min_raise,
+            raise,
+        }
+    }
+
+    pub fn record_action(&mut self, player_index: usize, total_bet: u32) -> bool { // Enregistre la nouvelle mise totale d'un joueur; renvoie vrai si elle relance
+        let raised = total_bet > self.current_bet;
+        if raised {
+            if total_bet >= self.full_raise_level + self.min_raise { // Relance complète (seule ou cumulée avec des tapis incomplets): elle rouvre l'action
+                self.bets += 1; // Un tapis incomplet ne compte pas dans le plafond de relances
+                self.min_raise = self.min_raise.max(total_bet - self.current_bet);
+                self.full_raise_level = total_bet;
+            }
+            self.current_bet = total_bet;
+        }
+        self.acted_at[player_index] = Some(self.current_bet);
+        raised
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn player(chips: u32, current_bet: u32) -> Player { // Joueur avec son tapis restant et sa mise de la rue
+        Player { chips, current_bet, ..Player::new("Joueur".to_string(), false) }
+    }
+
+    #[test]
+    fn raise_must_be_at_least_the_last_full_raise() {
+        let mut round = BettingRound::new(3, 20, 20, BettingStructure::NoLimit);
+        let legal = round.legal_actions(0, &player(1000, 0), 30);
+        assert_eq!(legal.raise, Ok(RaiseBounds { min_total: 40, max_total: 1000 }));
+        assert_eq!(legal.validate(Action::Raise(30)), Err(BetError::BelowMinRaise { min_total: 40, min_raise: 20 }));
+
+        assert!(round.record_action(0, 60)); // Relance de 40: la suivante doit ajouter au moins 40
+        let legal = round.legal_actions(1, &player(1000, 0), 90);
+        assert_eq!(legal.raise.as_ref().map(|bounds| bounds.min_total), Ok(100));
+        assert_eq!(legal.validate(Action::Raise(100)), Ok(100));
+    }
+
+    #[test]
+    fn incomplete_all_in_does_not_reopen_action() {
+        let mut round = BettingRound::new(3, 0, 20, BettingStructure::NoLimit);
+        round.record_action(0, 100);
+        assert!(round.record_action(1, 150)); // Tapis de 150: relance de 50, moins que les 100 requis
+        assert_eq!(round.current_bet, 150);
+        assert_eq!(round.min_raise, 100);
+
+        let opener = round.legal_actions(0, &player(900, 100), 250);
+        assert_eq!(opener.raise, Err(BetError::ActionNotReopened { to_call: 50 }));
+        assert_eq!(opener.validate(Action::Call), Ok(50));
+        assert_eq!(opener.nearest(Action::Raise(400)), Action::Call);
+
+        let next = round.legal_actions(2, &player(1000, 0), 250); // N'a pas encore agi: il peut relancer
+        assert_eq!(next.raise.map(|bounds| bounds.min_total), Ok(250));
+    }
+
+    #[test]
+    fn pot_limit_raise_is_capped_at_call_plus_pot() {
+        let round = BettingRound::new(3, 20, 20, BettingStructure::PotLimit);
+        let legal = round.legal_actions(0, &player(1000, 0), 30); // Blinds 10/20: suivre 20, puis relancer de 50
+        assert_eq!(legal.raise, Ok(RaiseBounds { min_total: 40, max_total: 70 }));
+        assert_eq!(legal.validate(Action::Raise(80)), Err(BetError::AboveMaxRaise { max_total: 70 }));
+
+        let short = round.legal_actions(1, &player(50, 10), 30); // Tapis plus court que la limite du pot
+        assert_eq!(short.raise.map(|bounds| bounds.max_total), Ok(60));
+    }
+
+    #[test]
+    fn fixed_limit_stops_raising_at_the_cap() {
+        let structure = BettingStructure::FixedLimit { small_bet: 10, big_bet: 20, raise_cap: 4 };
+        let mut round = BettingRound::new(5, 10, 10, structure);
+        assert_eq!(round.legal_actions(0, &player(1000, 0), 15).raise, Ok(RaiseBounds { min_total: 20, max_total: 20 }));
+        for (i, total) in [(0, 20), (1, 30), (2, 40)] { // Grosse blind, puis trois relances: quatre mises
+            round.record_action(i, total);
+        }
+        let capped = round.legal_actions(3, &player(1000, 0), 105);
+        assert_eq!(capped.raise, Err(BetError::RaiseCapReached { to_call: 40 }));
+        assert_eq!(capped.nearest(Action::Raise(50)), Action::Call);
+    }
+
+    #[test]
+    fn short_all_in_does_not_count_toward_the_raise_cap() {
+        let structure = BettingStructure::FixedLimit { small_bet: 10, big_bet: 20, raise_cap: 4 };
+        let mut round = BettingRound::new(5, 10, 10, structure);
+        round.record_action(0, 20);
+        round.record_action(1, 30);
+        assert!(round.record_action(2, 35)); // Tapis incomplet: trois mises seulement
+        let next = round.legal_actions(3, &player(1000, 0), 100);
+        assert_eq!(next.raise, Ok(RaiseBounds { min_total: 45, max_total: 45 }));
+        round.record_action(3, 45);
+        assert_eq!(round.legal_actions(4, &player(1000, 0), 145).raise, Err(BetError::RaiseCapReached { to_call: 45 }));
+    }
+}
@@ -0,0 +1,183 @@
+use std::fmt;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LevelDuration { // Durée d'un niveau: un nombre de mains ou de minutes
+    Hands(u32),
+    Minutes(u32),
+}
+
+impl fmt::Display for LevelDuration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LevelDuration::Hands(hands) => write!(f, "{} mains", hands),
+            LevelDuration::Minutes(minutes) => write!(f, "{} min", minutes),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlindLevel { // Un niveau de la structure: blinds, ante et durée
+    pub small_blind: u32,
+    pub big_blind: u32,
+    pub ante: u32,
+    pub duration: LevelDuration,
+}
+
+impl fmt::Display for BlindLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "blinds {}/{}", self.small_blind, self.big_blind)?;
+        if self.ante > 0 {
+            write!(f, ", ante {}", self.ante)?;
+        }
+        write!(f, " ({})", self.duration)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Preset { // Structures prédéfinies, de la plus rapide à la plus lente
+    Turbo,
+    Regular,
+    Deep,
+}
+
+impl FromStr for Preset {
+    type Err = ScheduleError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "turbo" => Ok(Preset::Turbo),
+            "regular" | "régulière" | "reguliere" => Ok(Preset::Regular),
+            "deep" => Ok(Preset::Deep),
+            other => Err(ScheduleError::UnknownPreset(other.to_string())),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScheduleError {
+    Io(String),
+    Line { line: usize, content: String },
+    Empty,
+    UnknownPreset(String),
+}
+
+impl fmt::Display for ScheduleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScheduleError::Io(error) => write!(f, "lecture de la structure impossible: {}", error),
+            ScheduleError::Line { line, content } => write!(f, "niveau invalide à la ligne {}: '{}' (attendu par exemple '25 50 5 10 mains' ou '25 50 5 15 min')", line, content),
+            ScheduleError::Empty => write!(f, "la structure ne contient aucun niveau"),
+            ScheduleError::UnknownPreset(name) => write!(f, "structure inconnue: '{}' (turbo, regular ou deep)", name),
+        }
+    }
+}
+
+impl std::error::Error for ScheduleError {}
+
+const PROGRESSION: [(u32, u32); 11] = [(5, 10), (10, 20), (15, 30), (25, 50), (50, 100), (75, 150), (100, 200), (150, 300), (200, 400), (300, 600), (500, 1000)];
+
+#[derive(Debug, Clone)]
+pub struct BlindSchedule { // Structure de tournoi: les niveaux se succèdent, le dernier dure indéfiniment
+    levels: Vec<BlindLevel>,
+    current: usize,
+    hands_at_level: u32,
+    level_started: Option<Instant>, // None tant qu'aucune main n'a été jouée
+}
+
+impl BlindSchedule {
+    pub fn new(levels: Vec<BlindLevel>) -> Result<Self, ScheduleError> {
+        if levels.is_empty() {
+            return Err(ScheduleError::Empty);
+        }
+        Ok(Self { levels, current: 0, hands_at_level: 0, level_started: None })
+    }
+
+    pub fn preset(preset: Preset) -> Self { // Turbo: 5 mains par niveau; régulière: 10; deep: 15, en partant de blinds plus petites
+        let (hands, first_ante_level, start): (u32, usize, &[(u32, u32)]) = match preset {
+            Preset::Turbo => (5, 2, &[]),
+            Preset::Regular => (10, 3, &[]),
+            Preset::Deep => (15, 6, &[(1, 2), (2, 4), (3, 6), (4, 8)]),
+        };
+        let levels = start.iter().chain(PROGRESSION.iter())
+            .enumerate()
+            .map(|(i, &(small_blind, big_blind))| BlindLevel {
+                small_blind,
+                big_blind,
+                ante: if i >= first_ante_level { big_blind / 10 } else { 0 },
+                duration: LevelDuration::Hands(hands),
+            })
+            .collect();
+        Self::new(levels).expect("les structures prédéfinies ont des niveaux")
+    }
+
+    pub fn from_file(path: &str) -> Result<Self, ScheduleError> { // Un niveau par ligne: petite blind, grosse blind, ante, durée
+        std::fs::read_to_string(path)
+            .map_err(|error| ScheduleError::Io(error.to_string()))?
+            .parse()
+    }
+
+    pub fn level(&self) -> &BlindLevel {
+        &self.levels[self.current]
+    }
+
+    pub fn level_number(&self) -> usize { // Numéro du niveau en cours, à partir de 1
+        self.current + 1
+    }
+
+    pub fn start_hand(&mut self) -> Option<&BlindLevel> { // À appeler avant chaque main; renvoie le nouveau niveau s'il vient de changer
+        self.start_hand_at(Instant::now())
+    }
+
+    pub fn start_hand_at(&mut self, now: Instant) -> Option<&BlindLevel> {
+        let Some(started) = self.level_started else { // Première main: le premier niveau commence
+            self.level_started = Some(now);
+            self.hands_at_level = 1;
+            return Some(&self.levels[0]);
+        };
+        let finished = match self.level().duration {
+            LevelDuration::Hands(hands) => self.hands_at_level >= hands,
+            LevelDuration::Minutes(minutes) => now.duration_since(started) >= Duration::from_secs(60 * minutes as u64),
+        };
+        if finished && self.current + 1 < self.levels.len() {
+            self.current += 1;
+            self.hands_at_level = 1;
+            self.level_started = Some(now);
+            return Some(&self.levels[self.current]);
+        }
+        self.hands_at_level += 1;
+        None
+    }
+}
+
+impl FromStr for BlindSchedule { // Lignes "5 10 0 10 mains" ou "25 50 5 15 min"; les lignes vides et les commentaires (#) sont ignorés
+    type Err = ScheduleError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut levels = Vec::new();
+        for (number, line) in s.lines().enumerate() {
+            let content = line.split('#').next().unwrap_or("").trim();
+            if content.is_empty() {
+                continue;
+            }
+            let invalid = || ScheduleError::Line { line: number + 1, content: content.to_string() };
+            let fields: Vec<&str> = content.split_whitespace().collect();
+            let [small_blind, big_blind, ante, length, unit] = fields[..] else {
+                return Err(invalid());
+            };
+            let value = |field: &str| field.parse::<u32>().map_err(|_| invalid());
+            let (small_blind, big_blind, ante, length) = (value(small_blind)?, value(big_blind)?, value(ante)?, value(length)?);
+            let duration = match unit {
+                "main" | "mains" | "hands" => LevelDuration::Hands(length),
+                "min" | "minutes" => LevelDuration::Minutes(length),
+                _ => return Err(invalid()),
+            };
+            if big_blind == 0 || small_blind > big_blind || length == 0 {
+                return Err(invalid());
+            }
+            levels.push(BlindLevel { small_blind, big_blind, ante, duration });
+        }
+        Self::new(levels)
+    }
+}
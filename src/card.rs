@@ -1,4 +1,7 @@
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum Suit {
     Hearts,
     Spades,
@@ -6,40 +9,176 @@ pub enum Suit {
     Clubs,
 }
 
+impl Suit {
+    pub const ALL: [Suit; 4] = [Suit::Hearts, Suit::Spades, Suit::Diamonds, Suit::Clubs];
+}
+
+impl fmt::Display for Suit { // Notation courte: h, s, d, c
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let letter = match self {
+            Suit::Hearts => "h",
+            Suit::Spades => "s",
+            Suit::Diamonds => "d",
+            Suit::Clubs => "c",
+        };
+        write!(f, "{}", letter)
+    }
+}
+
+impl FromStr for Suit {
+    type Err = ParseCardError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "h" | "H" | "♥" => Ok(Suit::Hearts),
+            "s" | "S" | "♠" => Ok(Suit::Spades),
+            "d" | "D" | "♦" => Ok(Suit::Diamonds),
+            "c" | "C" | "♣" => Ok(Suit::Clubs),
+            _ => Err(ParseCardError::UnknownSuit(s.to_string())),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Rank { // La valeur de chaque variante est sa valeur au poker (l'As vaut 14)
+    Two = 2,
+    Three,
+    Four,
+    Five,
+    Six,
+    Seven,
+    Eight,
+    Nine,
+    Ten,
+    Jack,
+    Queen,
+    King,
+    Ace,
+}
+
+impl Rank {
+    pub const ALL: [Rank; 13] = [
+        Rank::Two, Rank::Three, Rank::Four, Rank::Five, Rank::Six, Rank::Seven, Rank::Eight,
+        Rank::Nine, Rank::Ten, Rank::Jack, Rank::Queen, Rank::King, Rank::Ace,
+    ];
+
+    pub fn value(self) -> u32 {
+        self as u32
+    }
+}
+
+impl fmt::Display for Rank { // Notation courte: 2 à 9, T, J, Q, K, A
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let symbol = match self {
+            Rank::Ten => "T",
+            Rank::Jack => "J",
+            Rank::Queen => "Q",
+            Rank::King => "K",
+            Rank::Ace => "A",
+            other => return write!(f, "{}", other.value()),
+        };
+        write!(f, "{}", symbol)
+    }
+}
+
+impl FromStr for Rank {
+    type Err = ParseCardError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_uppercase().as_str() {
+            "2" => Ok(Rank::Two),
+            "3" => Ok(Rank::Three),
+            "4" => Ok(Rank::Four),
+            "5" => Ok(Rank::Five),
+            "6" => Ok(Rank::Six),
+            "7" => Ok(Rank::Seven),
+            "8" => Ok(Rank::Eight),
+            "9" => Ok(Rank::Nine),
+            "T" | "10" => Ok(Rank::Ten),
+            "J" => Ok(Rank::Jack),
+            "Q" => Ok(Rank::Queen),
+            "K" => Ok(Rank::King),
+            "A" => Ok(Rank::Ace),
+            _ => Err(ParseCardError::UnknownRank(s.to_string())),
+        }
+    }
+}
 
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseCardError { // Erreurs de lecture d'une carte en notation courte
+    UnknownRank(String),
+    UnknownSuit(String),
+    Malformed(String),
+}
+
+impl fmt::Display for ParseCardError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseCardError::UnknownRank(s) => write!(f, "rang de carte invalide: '{}'", s),
+            ParseCardError::UnknownSuit(s) => write!(f, "couleur de carte invalide: '{}'", s),
+            ParseCardError::Malformed(s) => write!(f, "carte invalide: '{}' (attendu par exemple Ah, Td, 2c)", s),
+        }
+    }
+}
+
+impl std::error::Error for ParseCardError {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Card {
-    pub rank: String,
+    pub rank: Rank,
     pub suit: Suit,
 }
 
-impl Card {
-    pub fn to_string(&self) -> String {
+impl fmt::Display for Card { // Affichage terminal avec les symboles de couleur
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let suit_symbol = match self.suit {
             Suit::Hearts => "♥️",
             Suit::Spades => "♠️",
             Suit::Diamonds => "♦️",
             Suit::Clubs => "♣️",
         };
-        format!("{}{}", self.rank, suit_symbol)
+        match self.rank {
+            Rank::Ten => write!(f, "10{}", suit_symbol),
+            rank => write!(f, "{}{}", rank, suit_symbol),
+        }
+    }
+}
+
+impl FromStr for Card {
+    type Err = ParseCardError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim().trim_end_matches('\u{FE0F}'); // Accepte aussi les symboles affichés par le jeu
+        let split = match s.char_indices().last() { // Le dernier caractère est la couleur, le reste le rang
+            Some((index, _)) if index > 0 => index,
+            _ => return Err(ParseCardError::Malformed(s.to_string())),
+        };
+        let (rank, suit) = s.split_at(split);
+        Ok(Card::new(rank.parse()?, suit.parse()?))
+    }
+}
+
+impl Card {
+    pub fn new(rank: Rank, suit: Suit) -> Self {
+        Self { rank, suit }
+    }
+
+    pub fn parse(s: &str) -> Result<Self, ParseCardError> { // Lit une carte en notation courte, par exemple "Ah", "Td" ou "2c"
+        s.parse()
+    }
+
+    pub fn notation(&self) -> String { // Notation courte de la carte, inverse de `Card::parse`
+        format!("{}{}", self.rank, self.suit)
     }
 
     pub fn rank_value(&self) -> u32 {
-        match self.rank.as_str() {
-            "2" => 2,
-            "3" => 3,
-            "4" => 4,
-            "5" => 5,
-            "6" => 6,
-            "7" => 7,
-            "8" => 8,
-            "9" => 9,
-            "10" => 10,
-            "J" => 11,
-            "Q" => 12,
-            "K" => 13,
-            "A" => 14,
-            _ => 0,
-        }
+        self.rank.value()
     }
 }
+
+pub fn parse_cards(s: &str) -> Result<Vec<Card>, ParseCardError> { // Lit une liste de cartes séparées par des espaces ou des virgules, par exemple "Ah Kd 2c"
+    s.split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|token| !token.is_empty())
+        .map(Card::parse)
+        .collect()
+}
@@ -0,0 +1,111 @@
+use crate::strategy::Difficulty;
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GameMode { // Tournoi jusqu'au dernier survivant, ou partie cash avec recaves
+    #[default]
+    Tournament,
+    Cash { max_buy_in: u32 }, // Tapis maximal après une cave ou une recave
+}
+
+impl fmt::Display for GameMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GameMode::Tournament => write!(f, "tournoi"),
+            GameMode::Cash { max_buy_in } => write!(f, "partie cash (cave maximale {})", max_buy_in),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TableCommand { // Commandes saisies entre deux mains d'une partie cash
+    Continue,
+    Rebuy { name: String, amount: u32 },
+    SitOut(String),
+    SitIn(String),
+    Leave(String),
+    JoinHuman { name: String, amount: u32 },
+    JoinAi { difficulty: Difficulty, amount: u32 },
+    End,
+}
+
+impl FromStr for TableCommand { // "recave 50 Alice", "pause Alice", "retour Alice", "quitter Alice", "rejoindre 100 Bob", "rejoindre 100 ia 3", "fin"
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let words: Vec<&str> = s.split_whitespace().collect();
+        let amount = |word: Option<&&str>| word.and_then(|w| w.parse::<u32>().ok()).ok_or_else(|| "montant manquant ou invalide".to_string());
+        let name = |from: usize| match words.get(from..) {
+            Some(rest) if !rest.is_empty() => Ok(rest.join(" ")),
+            _ => Err("nom du joueur manquant".to_string()),
+        };
+        match words.first().map(|w| w.to_lowercase()).as_deref() {
+            None => Ok(TableCommand::Continue),
+            Some("fin") => Ok(TableCommand::End),
+            Some("recave") => Ok(TableCommand::Rebuy { amount: amount(words.get(1))?, name: name(2)? }),
+            Some("pause") => Ok(TableCommand::SitOut(name(1)?)),
+            Some("retour") => Ok(TableCommand::SitIn(name(1)?)),
+            Some("quitter") => Ok(TableCommand::Leave(name(1)?)),
+            Some("rejoindre") => {
+                let amount = amount(words.get(1))?;
+                if words.get(2).is_some_and(|w| w.eq_ignore_ascii_case("ia")) {
+                    let difficulty = words.get(3)
+                        .and_then(|level| level.parse().ok())
+                        .and_then(Difficulty::from_level)
+                        .ok_or_else(|| "niveau d'IA invalide (1 à 4)".to_string())?;
+                    Ok(TableCommand::JoinAi { difficulty, amount })
+                } else {
+                    Ok(TableCommand::JoinHuman { name: name(2)?, amount })
+                }
+            }
+            Some(other) => Err(format!("commande inconnue: '{}'", other)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CashGameError {
+    NotCashGame,
+    UnknownPlayer(String),
+    NameTaken(String),
+    TableFull,
+    ZeroBuyIn,
+    AboveMaxBuyIn { max_buy_in: u32 },
+    NoChips(String),
+}
+
+impl fmt::Display for CashGameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CashGameError::NotCashGame => write!(f, "uniquement en partie cash"),
+            CashGameError::UnknownPlayer(name) => write!(f, "aucun joueur nommé '{}' à la table", name),
+            CashGameError::NameTaken(name) => write!(f, "le nom '{}' est déjà pris", name),
+            CashGameError::TableFull => write!(f, "la table est complète"),
+            CashGameError::ZeroBuyIn => write!(f, "la cave doit être positive"),
+            CashGameError::AboveMaxBuyIn { max_buy_in } => write!(f, "le tapis ne peut pas dépasser la cave maximale de {}", max_buy_in),
+            CashGameError::NoChips(name) => write!(f, "{} n'a plus de jetons et doit d'abord se recaver", name),
+        }
+    }
+}
+
+impl std::error::Error for CashGameError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SessionResult { // Bilan d'un joueur en fin de session
+    pub name: String,
+    pub buy_in: u32, // Total des caves et recaves
+    pub cash_out: u32, // Jetons récupérés en quittant la table
+}
+
+impl SessionResult {
+    pub fn net(&self) -> i64 {
+        self.cash_out as i64 - self.buy_in as i64
+    }
+}
+
+impl fmt::Display for SessionResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: caves {}, jetons {}, résultat {:+}", self.name, self.buy_in, self.cash_out, self.net())
+    }
+}
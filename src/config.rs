@@ -0,0 +1,271 @@
+use poker_game::betting::BettingStructure;
+use poker_game::blinds::{BlindSchedule, Preset, ScheduleError};
+use poker_game::strategy::Difficulty;
+use poker_game::tournament::PayoutTable;
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer};
+use std::fmt;
+use std::str::FromStr;
+
+pub const USAGE: &str = "\
+Usage: poker_game [options]
+
+Les réglages absents sont demandés au clavier, sauf avec --config ou --defaults
+où ils prennent leur valeur par défaut. Les options l'emportent sur le fichier.
+
+  -c, --config <fichier>   Réglages au format TOML, ou JSON si le fichier finit par .json
+      --defaults           Ne pose aucune question
+      --humans <noms>      Joueurs humains, par exemple 'Alice,Bob', ou leur nombre
+      --ai <niveaux>       Une IA par niveau (1 à 4), par exemple '1,3,4' (une IA facile par défaut)
+      --stack <jetons>     Tapis de départ
+      --blinds <pb/gb>     Blinds, par exemple 5/10
+      --ante <jetons>      Ante payée par chaque joueur
+      --bb-ante            Ante payée par la grosse blind pour toute la table
+      --straddle           Straddle du joueur après la grosse blind
+      --seats <nombre>     Nombre de sièges (2 à 10)
+      --structure <nom>    no-limit, pot-limit ou fixed-limit
+      --mode <nom>         tournament ou cash
+      --max-buy-in <jetons> Cave maximale en partie cash
+      --schedule <nom>     Structure des blinds: fixed, turbo, regular, deep ou un fichier de niveaux
+      --prize-pool <jetons> Dotation du tournoi
+      --payouts <pcts>     Répartition des gains, par exemple 50/30/20
+      --seed <nombre>      Graine du mélange, pour rejouer les mêmes donnes
+  -h, --help               Affiche cette aide";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+#[allow(clippy::enum_variant_names)] // Mêmes noms que BettingStructure
+pub enum Structure { // Structure de mise choisie; les mises de la limite fixe découlent de la grosse blind
+    NoLimit,
+    PotLimit,
+    FixedLimit,
+}
+
+impl Structure {
+    pub fn betting_structure(self, big_blind: u32) -> BettingStructure {
+        match self {
+            Structure::NoLimit => BettingStructure::NoLimit,
+            Structure::PotLimit => BettingStructure::PotLimit,
+            Structure::FixedLimit => BettingStructure::fixed_limit(big_blind),
+        }
+    }
+}
+
+impl FromStr for Structure {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "nl" | "no-limit" => Ok(Structure::NoLimit),
+            "pl" | "pot-limit" => Ok(Structure::PotLimit),
+            "fl" | "limit" | "fixed-limit" => Ok(Structure::FixedLimit),
+            other => Err(format!("structure inconnue: '{}' (no-limit, pot-limit ou fixed-limit)", other)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Mode { // Mode de jeu choisi; la cave maximale d'une partie cash est un réglage à part
+    #[serde(alias = "tournoi")]
+    Tournament,
+    Cash,
+}
+
+impl FromStr for Mode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "tournament" | "tournoi" => Ok(Mode::Tournament),
+            "cash" => Ok(Mode::Cash),
+            other => Err(format!("mode inconnu: '{}' (tournament ou cash)", other)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct GameSetup { // Réglages lus dans un fichier ou sur la ligne de commande; None quand le réglage n'est pas donné
+    pub humans: Option<Vec<String>>,
+    #[serde(deserialize_with = "ai_levels")]
+    pub ai: Option<Vec<Difficulty>>,
+    pub starting_stack: Option<u32>,
+    pub small_blind: Option<u32>,
+    pub big_blind: Option<u32>,
+    pub ante: Option<u32>,
+    pub big_blind_ante: Option<bool>,
+    pub straddle: Option<bool>,
+    pub max_seats: Option<usize>,
+    pub structure: Option<Structure>,
+    pub mode: Option<Mode>,
+    pub max_buy_in: Option<u32>,
+    pub blind_schedule: Option<String>, // Nom d'une structure prédéfinie ou chemin d'un fichier de niveaux
+    pub prize_pool: Option<u32>,
+    #[serde(deserialize_with = "parsed")]
+    pub payouts: Option<PayoutTable>,
+    pub seed: Option<u64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CommandLine {
+    Help,
+    Play { setup: GameSetup, interactive: bool }, // interactive: les réglages absents sont demandés au clavier
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SetupError {
+    Io { path: String, error: String },
+    File { path: String, error: String },
+    UnknownOption(String),
+    MissingValue(String),
+    InvalidValue { option: String, error: String },
+}
+
+impl fmt::Display for SetupError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SetupError::Io { path, error } => write!(f, "lecture de '{}' impossible: {}", path, error),
+            SetupError::File { path, error } => write!(f, "configuration invalide dans '{}': {}", path, error),
+            SetupError::UnknownOption(option) => write!(f, "option inconnue: '{}'", option),
+            SetupError::MissingValue(option) => write!(f, "valeur manquante pour {}", option),
+            SetupError::InvalidValue { option, error } => write!(f, "valeur invalide pour {}: {}", option, error),
+        }
+    }
+}
+
+impl std::error::Error for SetupError {}
+
+fn ai_levels<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Vec<Difficulty>>, D::Error> { // Niveaux 1 à 4, comme au clavier
+    Option::<Vec<u32>>::deserialize(deserializer)?
+        .map(|levels| levels.into_iter()
+            .map(|level| Difficulty::from_level(level).ok_or_else(|| D::Error::custom(format!("niveau d'IA invalide: {} (1 à 4)", level))))
+            .collect())
+        .transpose()
+}
+
+fn parsed<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error> // Valeur écrite dans la même notation qu'au clavier
+where
+    D: Deserializer<'de>,
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    Option::<String>::deserialize(deserializer)?
+        .map(|value| value.parse().map_err(D::Error::custom))
+        .transpose()
+}
+
+fn value<T>(option: &str, value: Option<String>) -> Result<T, SetupError> // Valeur d'une option, lue avec FromStr
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    let value = value.ok_or_else(|| SetupError::MissingValue(option.to_string()))?;
+    value.trim().parse().map_err(|error: T::Err| SetupError::InvalidValue { option: option.to_string(), error: error.to_string() })
+}
+
+pub fn human_names(value: &str) -> Vec<String> { // "Alice,Bob" ou un nombre de joueurs, nommés Joueur 1, Joueur 2...
+    match value.trim().parse::<usize>() {
+        Ok(count) => (1..=count).map(|i| format!("Joueur {}", i)).collect(),
+        Err(_) => value.split(',').map(|name| name.trim().to_string()).filter(|name| !name.is_empty()).collect(),
+    }
+}
+
+pub fn load_schedule(choice: &str) -> Result<Option<BlindSchedule>, ScheduleError> { // Numéro du menu, nom d'une structure ou fichier de niveaux; None pour des blinds fixes
+    match choice.trim() {
+        "" | "1" | "fixed" | "fixes" => Ok(None),
+        "2" => Ok(Some(BlindSchedule::preset(Preset::Turbo))),
+        "3" => Ok(Some(BlindSchedule::preset(Preset::Regular))),
+        "4" => Ok(Some(BlindSchedule::preset(Preset::Deep))),
+        choice => match choice.parse::<Preset>() {
+            Ok(preset) => Ok(Some(BlindSchedule::preset(preset))),
+            Err(_) => BlindSchedule::from_file(choice).map(Some),
+        },
+    }
+}
+
+impl GameSetup {
+    pub fn from_file(path: &str) -> Result<Self, SetupError> { // TOML par défaut, JSON pour les fichiers .json
+        let content = std::fs::read_to_string(path).map_err(|error| SetupError::Io { path: path.to_string(), error: error.to_string() })?;
+        let invalid = |error: String| SetupError::File { path: path.to_string(), error };
+        if path.to_lowercase().ends_with(".json") {
+            serde_json::from_str(&content).map_err(|error| invalid(error.to_string()))
+        } else {
+            toml::from_str(&content).map_err(|error| invalid(error.to_string()))
+        }
+    }
+
+    pub fn from_args<I: IntoIterator<Item = String>>(args: I) -> Result<CommandLine, SetupError> { // Options de la ligne de commande, sans le nom du programme
+        let mut args = args.into_iter();
+        let mut options = GameSetup::default();
+        let mut file = None;
+        let mut interactive = true;
+        while let Some(option) = args.next() {
+            let option = option.as_str();
+            match option {
+                "-h" | "--help" => return Ok(CommandLine::Help),
+                "-c" | "--config" => {
+                    file = Some(value::<String>(option, args.next())?);
+                    interactive = false;
+                }
+                "--defaults" => interactive = false,
+                "--humans" => options.humans = Some(human_names(&value::<String>(option, args.next())?)),
+                "--ai" => {
+                    let levels = value::<String>(option, args.next())?;
+                    let difficulties = levels.split(',')
+                        .map(|level| level.trim().parse().ok().and_then(Difficulty::from_level))
+                        .collect::<Option<Vec<_>>>()
+                        .ok_or_else(|| SetupError::InvalidValue { option: option.to_string(), error: format!("'{}' (niveaux 1 à 4 séparés par des virgules)", levels) })?;
+                    options.ai = Some(difficulties);
+                }
+                "--stack" => options.starting_stack = Some(value(option, args.next())?),
+                "--blinds" => {
+                    let blinds = value::<String>(option, args.next())?;
+                    let (small_blind, big_blind) = blinds.split_once('/')
+                        .and_then(|(small, big)| Some((small.trim().parse().ok()?, big.trim().parse().ok()?)))
+                        .ok_or_else(|| SetupError::InvalidValue { option: option.to_string(), error: format!("'{}' (attendu par exemple 5/10)", blinds) })?;
+                    options.small_blind = Some(small_blind);
+                    options.big_blind = Some(big_blind);
+                }
+                "--ante" => options.ante = Some(value(option, args.next())?),
+                "--bb-ante" => options.big_blind_ante = Some(true),
+                "--straddle" => options.straddle = Some(true),
+                "--seats" => options.max_seats = Some(value(option, args.next())?),
+                "--structure" => options.structure = Some(value(option, args.next())?),
+                "--mode" => options.mode = Some(value(option, args.next())?),
+                "--max-buy-in" => options.max_buy_in = Some(value(option, args.next())?),
+                "--schedule" => options.blind_schedule = Some(value(option, args.next())?),
+                "--prize-pool" => options.prize_pool = Some(value(option, args.next())?),
+                "--payouts" => options.payouts = Some(value(option, args.next())?),
+                "--seed" => options.seed = Some(value(option, args.next())?),
+                other => return Err(SetupError::UnknownOption(other.to_string())),
+            }
+        }
+        let setup = match file {
+            Some(path) => Self::from_file(&path)?.merge(options),
+            None => options,
+        };
+        Ok(CommandLine::Play { setup, interactive })
+    }
+
+    pub fn merge(self, overrides: GameSetup) -> GameSetup { // Les réglages de `overrides` l'emportent
+        GameSetup {
+            humans: overrides.humans.or(self.humans),
+            ai: overrides.ai.or(self.ai),
+            starting_stack: overrides.starting_stack.or(self.starting_stack),
+            small_blind: overrides.small_blind.or(self.small_blind),
+            big_blind: overrides.big_blind.or(self.big_blind),
+            ante: overrides.ante.or(self.ante),
+            big_blind_ante: overrides.big_blind_ante.or(self.big_blind_ante),
+            straddle: overrides.straddle.or(self.straddle),
+            max_seats: overrides.max_seats.or(self.max_seats),
+            structure: overrides.structure.or(self.structure),
+            mode: overrides.mode.or(self.mode),
+            max_buy_in: overrides.max_buy_in.or(self.max_buy_in),
+            blind_schedule: overrides.blind_schedule.or(self.blind_schedule),
+            prize_pool: overrides.prize_pool.or(self.prize_pool),
+            payouts: overrides.payouts.or(self.payouts),
+            seed: overrides.seed.or(self.seed),
+        }
+    }
+}
@@ -0,0 +1,52 @@
+use crate::card::{Card, Rank, Suit};
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Deck { // Paquet de cartes: les cartes sont tirées depuis la fin du vecteur
+    cards: Vec<Card>,
+}
+
+impl Deck {
+    pub fn new() -> Self { // Paquet complet de 52 cartes, non mélangé
+        let mut cards = Vec::with_capacity(52);
+        for suit in Suit::ALL {
+            for rank in Rank::ALL {
+                cards.push(Card::new(rank, suit));
+            }
+        }
+        Self { cards }
+    }
+
+    pub fn shuffled<R: Rng + ?Sized>(rng: &mut R) -> Self { // Paquet complet mélangé avec le générateur donné
+        let mut deck = Self::new();
+        deck.shuffle(rng);
+        deck
+    }
+
+    pub fn shuffle<R: Rng + ?Sized>(&mut self, rng: &mut R) { // Mélange reproductible: même générateur, même ordre
+        self.cards.shuffle(rng);
+    }
+
+    pub fn deal(&mut self) -> Option<Card> { // Tire la carte du dessus
+        self.cards.pop()
+    }
+
+    pub fn burn(&mut self) { // Brûle la carte du dessus avant de distribuer une rue
+        self.cards.pop();
+    }
+
+    pub fn remove(&mut self, cards: &[Card]) { // Retire des cartes déjà connues (mains, tableau) du paquet
+        self.cards.retain(|c| !cards.contains(c));
+    }
+
+    pub fn cards(&self) -> &[Card] { // Cartes restantes, la prochaine distribuée en dernier
+        &self.cards
+    }
+}
+
+impl Default for Deck {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,345 @@
+use crate::card::{Card, ParseCardError, Rank, Suit};
+use crate::deck::Deck;
+use crate::evaluator::{evaluate, CardSet};
+use rand::seq::SliceRandom;
+use rand::Rng;
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Equity { // Résultat d'un calcul d'équité, chaque valeur entre 0 et 1
+    pub win: f64,
+    pub tie: f64,
+    pub lose: f64,
+    pub equity: f64, // Part du pot attendue: victoires plus la part des égalités
+    pub exact: bool, // Vrai si toutes les combinaisons ont été énumérées
+}
+
+impl fmt::Display for Equity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "victoire {:.1}%, égalité {:.1}%, défaite {:.1}%", self.win * 100.0, self.tie * 100.0, self.lose * 100.0)?;
+        if self.exact {
+            write!(f, ", calcul exact")?;
+        }
+        Ok(())
+    }
+}
+
+const EXACT_ENUMERATION_LIMIT: u64 = 100_000; // Au-delà de ce nombre de cas, l'équité est estimée par Monte Carlo
+const EXACT_RUNOUT_LIMIT: u64 = 2_000_000; // Mains adverses toutes connues: seuls les tableaux sont énumérés (C(48,5) = 1 712 304 pour un tapis en tête-à-tête avant le flop)
+
+#[derive(Debug, Default)]
+struct EquityTally { // Compteurs accumulés sur chaque tableau simulé ou énuméré
+    wins: f64,
+    ties: f64,
+    share: f64,
+    boards: u64,
+}
+
+impl EquityTally {
+    fn record(&mut self, hero: &[Card], board: &[Card], opponents: &[[Card; 2]]) { // Compare le héros à tous les adversaires sur un tableau complet
+        let board = CardSet::from_cards(board);
+        let hero_rank = evaluate(board.union(CardSet::from_cards(hero)));
+        let mut tied = 0;
+        for opponent in opponents {
+            let rank = evaluate(board.union(CardSet::from_cards(opponent)));
+            if rank > hero_rank {
+                self.boards += 1;
+                return;
+            }
+            if rank == hero_rank {
+                tied += 1;
+            }
+        }
+        if tied == 0 {
+            self.wins += 1.0;
+            self.share += 1.0;
+        } else {
+            self.ties += 1.0;
+            self.share += 1.0 / (tied + 1) as f64;
+        }
+        self.boards += 1;
+    }
+
+    fn finish(&self) -> Equity {
+        if self.boards == 0 {
+            return Equity::default();
+        }
+        let total = self.boards as f64;
+        Equity {
+            win: self.wins / total,
+            tie: self.ties / total,
+            lose: (total - self.wins - self.ties) / total,
+            equity: self.share / total,
+            exact: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Range { // Ensemble de mains de départ possibles pour un adversaire
+    combos: Vec<[Card; 2]>,
+}
+
+impl Range {
+    pub fn exact(first: Card, second: Card) -> Self { // Main connue, par exemple un joueur à tapis qui a retourné ses cartes
+        Self { combos: vec![[first, second]] }
+    }
+
+    pub fn any() -> Self { // Les 1326 mains de départ possibles
+        let cards = Deck::new().cards().to_vec();
+        let mut combos = Vec::with_capacity(1326);
+        for (i, &first) in cards.iter().enumerate() {
+            for &second in &cards[i + 1..] {
+                combos.push([first, second]);
+            }
+        }
+        Self { combos }
+    }
+
+    fn add_pair(&mut self, rank: Rank) {
+        for (i, &first) in Suit::ALL.iter().enumerate() {
+            for &second in &Suit::ALL[i + 1..] {
+                self.combos.push([Card::new(rank, first), Card::new(rank, second)]);
+            }
+        }
+    }
+
+    fn add_unpaired(&mut self, high: Rank, low: Rank, suited: Option<bool>) { // suited: Some(true) assorties, Some(false) dépareillées, None les deux
+        for &first in &Suit::ALL {
+            for &second in &Suit::ALL {
+                if suited.is_none_or(|s| s == (first == second)) {
+                    self.combos.push([Card::new(high, first), Card::new(low, second)]);
+                }
+            }
+        }
+    }
+
+    fn sample<R: Rng + ?Sized>(&self, dead: &[Card], rng: &mut R) -> Option<[Card; 2]> { // Tire une main compatible avec les cartes déjà utilisées
+        for _ in 0..32 { // Tirage par rejet, rapide quand la plupart des mains sont libres
+            let combo = self.combos.choose(rng)?;
+            if !dead.contains(&combo[0]) && !dead.contains(&combo[1]) {
+                return Some(*combo);
+            }
+        }
+        let live: Vec<&[Card; 2]> = self.combos.iter()
+            .filter(|c| !dead.contains(&c[0]) && !dead.contains(&c[1]))
+            .collect();
+        live.choose(rng).map(|c| **c)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseRangeError {
+    Card(ParseCardError),
+    Token(String),
+}
+
+impl fmt::Display for ParseRangeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseRangeError::Card(error) => write!(f, "{}", error),
+            ParseRangeError::Token(token) => write!(f, "main invalide dans la range: '{}' (attendu par exemple QQ+, AKs, T9o, AhKh)", token),
+        }
+    }
+}
+
+impl std::error::Error for ParseRangeError {}
+
+impl From<ParseCardError> for ParseRangeError {
+    fn from(error: ParseCardError) -> Self {
+        ParseRangeError::Card(error)
+    }
+}
+
+impl FromStr for Range { // Notation usuelle séparée par des virgules: "QQ+, AKs, ATs+, KQo, AhKh"
+    type Err = ParseRangeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut range = Range { combos: Vec::new() };
+        for token in s.split(',').map(str::trim).filter(|t| !t.is_empty()) {
+            let chars: Vec<char> = token.chars().collect();
+            let invalid = || ParseRangeError::Token(token.to_string());
+            if token.is_ascii() && chars.len() == 4 && chars[1].is_alphabetic() && chars[1].is_lowercase() { // Main exacte, par exemple AhKh
+                let first = Card::parse(&token[..2])?;
+                let second = Card::parse(&token[2..])?;
+                range.combos.push([first, second]);
+                continue;
+            }
+            let plus = token.ends_with('+');
+            let body = token.trim_end_matches('+');
+            let mut body_chars = body.chars();
+            let (first, second) = match (body_chars.next(), body_chars.next()) {
+                (Some(a), Some(b)) => (a.to_string().parse::<Rank>()?, b.to_string().parse::<Rank>()?),
+                _ => return Err(invalid()),
+            };
+            let suited = match body_chars.next() {
+                None => None,
+                Some('s') => Some(true),
+                Some('o') => Some(false),
+                Some(_) => return Err(invalid()),
+            };
+            if body_chars.next().is_some() {
+                return Err(invalid());
+            }
+            let (high, low) = if first >= second { (first, second) } else { (second, first) };
+            if high == low {
+                if suited.is_some() {
+                    return Err(invalid());
+                }
+                let top = if plus { Rank::Ace } else { high };
+                for rank in Rank::ALL.iter().filter(|&&r| r >= high && r <= top) {
+                    range.add_pair(*rank);
+                }
+            } else {
+                let top = if plus { Rank::ALL[high as usize - 3] } else { low }; // Avec '+', le kicker monte jusqu'à la carte sous la plus haute
+                for kicker in Rank::ALL.iter().filter(|&&r| r >= low && r <= top) {
+                    range.add_unpaired(high, *kicker, suited);
+                }
+            }
+        }
+        Ok(range)
+    }
+}
+
+pub fn calculate_equity<R: Rng + ?Sized>(hero: &[Card], board: &[Card], opponents: usize, iterations: usize, rng: &mut R) -> Equity { // Équité contre des adversaires aux mains inconnues
+    let ranges = vec![Range::any(); opponents];
+    calculate_equity_vs_ranges(hero, board, &ranges, iterations, rng)
+}
+
+pub fn calculate_equity_vs_ranges<R: Rng + ?Sized>(hero: &[Card], board: &[Card], ranges: &[Range], iterations: usize, rng: &mut R) -> Equity { // Calcul exact si peu de cas restent, Monte Carlo sinon
+    let limit = if ranges.iter().all(|range| range.combos.len() == 1) { EXACT_RUNOUT_LIMIT } else { EXACT_ENUMERATION_LIMIT };
+    if enumeration_size(hero, board, ranges) <= limit {
+        exact_equity(hero, board, ranges)
+    } else {
+        simulate_equity_vs_ranges(hero, board, ranges, iterations, rng)
+    }
+}
+
+fn enumeration_size(hero: &[Card], board: &[Card], ranges: &[Range]) -> u64 { // Majorant du nombre de cas à énumérer (mains adverses et tableaux)
+    let unseen = 52u64.saturating_sub((hero.len() + board.len() + 2 * ranges.len()) as u64);
+    let missing = 5u64.saturating_sub(board.len() as u64);
+    let boards = (0..missing).fold(1u64, |acc, i| acc * (unseen - i) / (i + 1));
+    ranges.iter().fold(boards, |acc, range| acc.saturating_mul(range.combos.len() as u64))
+}
+
+pub fn exact_equity(hero: &[Card], board: &[Card], ranges: &[Range]) -> Equity { // Énumère toutes les mains adverses compatibles et tous les tableaux possibles
+    let mut tally = EquityTally::default();
+    let mut dead: Vec<Card> = hero.iter().chain(board).copied().collect();
+    let mut opponents = Vec::with_capacity(ranges.len());
+    enumerate_opponents(hero, board, ranges, &mut dead, &mut opponents, &mut tally);
+    Equity { exact: true, ..tally.finish() }
+}
+
+fn enumerate_opponents(hero: &[Card], board: &[Card], ranges: &[Range], dead: &mut Vec<Card>, opponents: &mut Vec<[Card; 2]>, tally: &mut EquityTally) {
+    let Some((range, rest)) = ranges.split_first() else {
+        let mut deck = Deck::new();
+        deck.remove(dead);
+        let mut full_board = board.to_vec();
+        enumerate_boards(hero, deck.cards(), 5usize.saturating_sub(board.len()), &mut full_board, opponents, tally);
+        return;
+    };
+    for combo in &range.combos {
+        if dead.contains(&combo[0]) || dead.contains(&combo[1]) {
+            continue;
+        }
+        dead.extend_from_slice(combo);
+        opponents.push(*combo);
+        enumerate_opponents(hero, board, rest, dead, opponents, tally);
+        opponents.pop();
+        dead.truncate(dead.len() - 2);
+    }
+}
+
+fn enumerate_boards(hero: &[Card], remaining: &[Card], missing: usize, board: &mut Vec<Card>, opponents: &[[Card; 2]], tally: &mut EquityTally) {
+    if missing == 0 {
+        tally.record(hero, board, opponents);
+        return;
+    }
+    for (i, &card) in remaining.iter().enumerate() {
+        board.push(card);
+        enumerate_boards(hero, &remaining[i + 1..], missing - 1, board, opponents, tally);
+        board.pop();
+    }
+}
+
+pub fn simulate_equity_vs_ranges<R: Rng + ?Sized>(hero: &[Card], board: &[Card], ranges: &[Range], iterations: usize, rng: &mut R) -> Equity { // Monte Carlo: tire les mains adverses dans leurs ranges et complète le tableau au hasard
+    let mut tally = EquityTally::default();
+    let mut full_board = Vec::with_capacity(5);
+    let mut opponents = Vec::with_capacity(ranges.len());
+    let mut dead = Vec::with_capacity(hero.len() + 5 + 2 * ranges.len());
+    'iterations: for _ in 0..iterations {
+        dead.clear();
+        dead.extend_from_slice(hero);
+        dead.extend_from_slice(board);
+        opponents.clear();
+        for range in ranges {
+            match range.sample(&dead, rng) {
+                Some(combo) => {
+                    dead.extend_from_slice(&combo);
+                    opponents.push(combo);
+                }
+                None => continue 'iterations, // Range incompatible avec les cartes connues
+            }
+        }
+        let mut deck = Deck::new();
+        deck.remove(&dead);
+        deck.shuffle(rng);
+        full_board.clear();
+        full_board.extend_from_slice(board);
+        while full_board.len() < 5 {
+            match deck.deal() {
+                Some(card) => full_board.push(card),
+                None => break,
+            }
+        }
+        tally.record(hero, &full_board, &opponents);
+    }
+    tally.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::card::parse_cards;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn cards(text: &str) -> Vec<Card> {
+        parse_cards(text).unwrap()
+    }
+
+    fn known(text: &str) -> Range {
+        let hand = cards(text);
+        Range::exact(hand[0], hand[1])
+    }
+
+    #[test]
+    fn river_result_is_certain() {
+        let equity = exact_equity(&cards("As Ad"), &cards("2c 7d 9h Jc 3s"), &[known("Ks Kd")]);
+        assert_eq!((equity.win, equity.tie, equity.lose, equity.equity), (1.0, 0.0, 0.0, 1.0));
+        assert!(equity.exact);
+    }
+
+    #[test]
+    fn turn_counts_the_outs_among_the_remaining_cards() {
+        let equity = exact_equity(&cards("As Ad"), &cards("2c 7d 9h Kc"), &[known("Ks Kd")]); // Seuls les deux derniers as battent le brelan
+        assert_eq!(equity.win, 2.0 / 44.0);
+        assert_eq!(equity.tie, 0.0);
+        assert!(equity.exact);
+    }
+
+    #[test]
+    fn board_that_plays_splits_the_pot() {
+        let equity = exact_equity(&cards("2c 3d"), &cards("Ts Js Qs Ks As"), &[known("4h 5h"), known("6c 7c")]);
+        assert_eq!((equity.tie, equity.equity), (1.0, 1.0 / 3.0));
+    }
+
+    #[test]
+    fn heads_up_all_in_before_the_flop_is_enumerated_exactly() {
+        let equity = calculate_equity_vs_ranges(&cards("As Ad"), &[], &[known("Ks Kd")], 1000, &mut StdRng::seed_from_u64(1));
+        assert!(equity.exact);
+        assert!((0.80..0.84).contains(&equity.equity), "{}", equity);
+        assert!((equity.win + equity.tie + equity.lose - 1.0).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,250 @@
+use crate::card::{Card, Rank, Suit};
+use crate::hand::{evaluate_hand, HandRank};
+use std::sync::OnceLock;
+
+const MAX_CARDS: usize = 7;
+const RANK_MASK: u64 = 0x1FFF; // 13 bits, un par rang
+const RANK_COLUMN: u64 = 0x0001_0001_0001_0001; // Le même rang dans les quatre couleurs
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct CardSet(u64); // Ensemble de cartes sur 64 bits: 16 bits par couleur, un bit par rang (le 2 au bit 0)
+
+impl CardSet {
+    pub fn from_cards(cards: &[Card]) -> Self {
+        let mut set = Self::default();
+        for &card in cards {
+            set.insert(card);
+        }
+        set
+    }
+
+    pub fn insert(&mut self, card: Card) {
+        self.0 |= Self::bit(card);
+    }
+
+    pub fn union(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+
+    pub fn len(self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    fn bit(card: Card) -> u64 {
+        1 << (card.suit as u64 * 16 + card.rank.value() as u64 - 2)
+    }
+
+    fn suit_mask(self, suit: usize) -> usize {
+        ((self.0 >> (suit * 16)) & RANK_MASK) as usize
+    }
+
+    fn rank_count(self, rank: usize) -> usize {
+        ((self.0 >> rank) & RANK_COLUMN).count_ones() as usize
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct HandValue(u16); // Force d'une main: plus la valeur est grande, meilleure est la main (même ordre que HandRank)
+
+impl HandValue {
+    pub fn rank(self) -> HandRank { // Catégorie et kickers correspondants
+        tables().ranks[self.0 as usize]
+    }
+}
+
+struct Tables { // Tables précalculées une seule fois avec l'évaluateur de référence
+    flush: Vec<u16>, // Indexée par le masque de rangs d'une couleur d'au moins cinq cartes
+    unsuited: Vec<Vec<u16>>, // Indexée par nombre de cartes puis par hachage parfait des effectifs de chaque rang
+    hash_steps: Vec<[[usize; 5]; MAX_CARDS + 1]>, // hash_steps[rang][cartes restantes][effectif]
+    ranks: Vec<HandRank>, // HandRank associé à chaque HandValue
+}
+
+fn tables() -> &'static Tables {
+    static TABLES: OnceLock<Tables> = OnceLock::new();
+    TABLES.get_or_init(build_tables)
+}
+
+pub fn evaluate(cards: CardSet) -> HandValue { // Évalue de 1 à 7 cartes sans allocation
+    let tables = tables();
+    for suit in 0..4 {
+        let mask = cards.suit_mask(suit);
+        if mask.count_ones() >= 5 { // Avec 7 cartes au plus, une couleur exclut carré et full
+            return HandValue(tables.flush[mask]);
+        }
+    }
+    let count = cards.len();
+    debug_assert!(count <= MAX_CARDS, "l'évaluateur rapide accepte au plus 7 cartes");
+    let mut remaining = count;
+    let mut index = 0;
+    for (rank, steps) in tables.hash_steps.iter().enumerate() {
+        let rank_count = cards.rank_count(rank);
+        index += steps[remaining][rank_count];
+        remaining -= rank_count;
+    }
+    HandValue(tables.unsuited[count][index])
+}
+
+pub fn evaluate_cards(hand: &[Card], community: &[Card]) -> HandValue { // Même interface que `evaluate_hand`
+    evaluate(CardSet::from_cards(hand).union(CardSet::from_cards(community)))
+}
+
+fn build_tables() -> Tables {
+    let combinations = rank_combinations();
+    let hash_steps: Vec<[[usize; 5]; MAX_CARDS + 1]> = (0..13)
+        .map(|rank| {
+            let mut steps = [[0; 5]; MAX_CARDS + 1];
+            for (remaining, row) in steps.iter_mut().enumerate() {
+                for count in 1..5 {
+                    let smaller = remaining.checked_sub(count - 1).map_or(0, |left| combinations[12 - rank][left]);
+                    row[count] = row[count - 1] + smaller;
+                }
+            }
+            steps
+        })
+        .collect();
+
+    let mut flush_ranks = vec![None; 1 << 13];
+    for (mask, slot) in flush_ranks.iter_mut().enumerate() {
+        let count = (mask as u32).count_ones();
+        if (5..=MAX_CARDS as u32).contains(&count) {
+            let cards: Vec<Card> = (0..13)
+                .filter(|bit| mask & (1 << bit) != 0)
+                .map(|bit| Card::new(Rank::ALL[bit], Suit::Hearts))
+                .collect();
+            *slot = Some(evaluate_hand(&cards, &[]));
+        }
+    }
+
+    let unsuited_ranks: Vec<Vec<HandRank>> = combinations[13].iter().enumerate()
+        .map(|(count, &size)| {
+            let mut ranks = Vec::with_capacity(size);
+            collect_unsuited(0, count, &mut [0; 13], &mut ranks);
+            ranks
+        })
+        .collect();
+
+    let mut ranks: Vec<HandRank> = flush_ranks.iter().flatten().chain(unsuited_ranks.iter().flatten()).copied().collect();
+    ranks.sort_unstable();
+    ranks.dedup();
+    let value_of = |rank: &HandRank| ranks.binary_search(rank).expect("rang présent dans la table") as u16;
+
+    Tables {
+        flush: flush_ranks.iter().map(|rank| rank.as_ref().map_or(0, value_of)).collect(),
+        unsuited: unsuited_ranks.iter().map(|table| table.iter().map(value_of).collect()).collect(),
+        hash_steps,
+        ranks,
+    }
+}
+
+fn rank_combinations() -> [[usize; MAX_CARDS + 1]; 14] { // combinations[r][n]: façons de répartir n cartes sur r rangs, 4 au plus par rang
+    let mut combinations = [[0; MAX_CARDS + 1]; 14];
+    combinations[0][0] = 1;
+    for ranks in 1..=13 {
+        for cards in 0..=MAX_CARDS {
+            combinations[ranks][cards] = (0..=cards.min(4)).map(|count| combinations[ranks - 1][cards - count]).sum();
+        }
+    }
+    combinations
+}
+
+fn collect_unsuited(rank: usize, remaining: usize, rank_counts: &mut [usize; 13], ranks: &mut Vec<HandRank>) { // Parcourt les effectifs par rang dans l'ordre du hachage parfait
+    if rank == 13 {
+        if remaining == 0 {
+            let mut cards = Vec::new();
+            for (rank, &count) in rank_counts.iter().enumerate() {
+                for _ in 0..count {
+                    let suit = Suit::ALL[cards.len() % 4]; // Couleurs réparties: jamais cinq cartes de la même couleur
+                    cards.push(Card::new(Rank::ALL[rank], suit));
+                }
+            }
+            ranks.push(evaluate_hand(&cards, &[]));
+        }
+        return;
+    }
+    for count in 0..=remaining.min(4) {
+        rank_counts[rank] = count;
+        collect_unsuited(rank + 1, remaining - count, rank_counts, ranks);
+    }
+    rank_counts[rank] = 0;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::deck::Deck;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use std::time::Instant;
+
+    #[test]
+    fn matches_reference_on_every_five_card_hand() {
+        let deck = Deck::new();
+        let cards = deck.cards();
+        let mut checked = 0;
+        for a in 0..52 {
+            for b in a + 1..52 {
+                for c in b + 1..52 {
+                    for d in c + 1..52 {
+                        for e in d + 1..52 {
+                            let hand = [cards[a], cards[b], cards[c], cards[d], cards[e]];
+                            assert_eq!(evaluate_cards(&hand, &[]).rank(), evaluate_hand(&hand, &[]), "{:?}", hand);
+                            checked += 1;
+                        }
+                    }
+                }
+            }
+        }
+        assert_eq!(checked, 2_598_960);
+    }
+
+    #[test]
+    fn matches_reference_on_random_seven_card_hands() {
+        let mut rng = StdRng::seed_from_u64(7);
+        for _ in 0..200_000 {
+            let mut deck = Deck::shuffled(&mut rng);
+            let hand: Vec<Card> = (0..7).filter_map(|_| deck.deal()).collect();
+            assert_eq!(evaluate_cards(&hand[..2], &hand[2..]).rank(), evaluate_hand(&hand[..2], &hand[2..]), "{:?}", hand);
+        }
+    }
+
+    #[test]
+    fn orders_hands_like_the_reference() {
+        let mut rng = StdRng::seed_from_u64(11);
+        for _ in 0..50_000 {
+            let mut deck = Deck::shuffled(&mut rng);
+            let first: Vec<Card> = (0..7).filter_map(|_| deck.deal()).collect();
+            let second: Vec<Card> = (0..7).filter_map(|_| deck.deal()).collect();
+            assert_eq!(
+                evaluate_cards(&first, &[]).cmp(&evaluate_cards(&second, &[])),
+                evaluate_hand(&first, &[]).cmp(&evaluate_hand(&second, &[])),
+            );
+        }
+    }
+
+    #[test]
+    #[ignore] // cargo test --release -- --ignored --nocapture benchmark
+    fn benchmark_seven_card_evaluations() {
+        let mut rng = StdRng::seed_from_u64(3);
+        let hands: Vec<CardSet> = (0..1_000_000)
+            .map(|_| {
+                let mut deck = Deck::shuffled(&mut rng);
+                let cards: Vec<Card> = (0..7).filter_map(|_| deck.deal()).collect();
+                CardSet::from_cards(&cards)
+            })
+            .collect();
+        evaluate(hands[0]); // Construction des tables hors chronométrage
+        let start = Instant::now();
+        let mut checksum = 0u64;
+        for _ in 0..10 {
+            for &hand in &hands {
+                checksum += evaluate(hand).0 as u64;
+            }
+        }
+        let elapsed = start.elapsed().as_secs_f64();
+        println!("{:.1} millions d'évaluations par seconde (somme de contrôle {})", 10.0 * hands.len() as f64 / elapsed / 1e6, checksum);
+    }
+}
@@ -0,0 +1,105 @@
+use crate::betting::Action;
+use crate::blinds::BlindLevel;
+use crate::card::Card;
+use crate::equity::Equity;
+use crate::hand::HandRank;
+use std::fmt;
+use std::sync::mpsc::Sender;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlindKind { // Mises forcées d'une main
+    Ante,
+    Small,
+    Big,
+    MissedBig, // Grosse blind manquée, payée au retour d'un joueur absent
+    Straddle,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum GameEvent { // Ce qui se passe à la table, dans l'ordre, pour les affichages, journaux, statistiques et rediffusions
+    HandStarted { stacks: Vec<(String, u32)> }, // Tapis de chaque joueur en jeu au début de la main
+    LevelChanged { level: usize, blinds: BlindLevel },
+    BlindPosted { player: String, kind: BlindKind, amount: u32, all_in: bool },
+    HoleCardsDealt { hands: Vec<(String, [Card; 2])> }, // Cartes privées: l'affichage public n'en donne que le nombre
+    PlayerActed { player: String, action: Action, amount: u32, all_in: bool }, // action: Fold, Check, Call, Bet ou Raise (mise totale); amount: jetons ajoutés
+    StreetDealt { street: String, cards: Vec<Card>, board: Vec<Card> }, // cards: cartes de la rue; board: tableau complet
+    HandRevealed { player: String, cards: [Card; 2], equity: Equity }, // Mains retournées quand plus personne ne peut miser
+    Showdown { player: String, cards: [Card; 2], rank: HandRank },
+    UncalledBetReturned { player: String, amount: u32 }, // Part d'une mise que personne n'a suivie, rendue avant le partage des pots
+    PotAwarded { player: String, amount: u32, pot: usize, winners: usize, uncontested: bool }, // pot: 0 pour le pot principal, puis les pots secondaires
+    PlayerEliminated { player: String, place: usize, prize: u32 },
+}
+
+fn cards_list(cards: &[Card]) -> String {
+    cards.iter().map(|c| c.to_string()).collect::<Vec<_>>().join(", ")
+}
+
+impl fmt::Display for GameEvent { // Texte affiché à la table, sans les informations privées
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GameEvent::HandStarted { .. } => write!(f, "+==================== Nouveau tour ====================+"),
+            GameEvent::LevelChanged { level, blinds } => write!(f, "| Niveau {}: {}.", level, blinds),
+            GameEvent::BlindPosted { player, kind, amount, all_in } => {
+                let name = match kind {
+                    BlindKind::Ante => "ante",
+                    BlindKind::Small => "small",
+                    BlindKind::Big => "big",
+                    BlindKind::MissedBig => "missed big",
+                    BlindKind::Straddle => "straddle",
+                };
+                match (all_in, kind) {
+                    (false, _) => write!(f, "| {} posted {} {}", player, amount, name),
+                    (true, BlindKind::Ante | BlindKind::Straddle) => write!(f, "| {} posted {} {} (all-in)", player, amount, name),
+                    (true, _) => write!(f, "| {} posted {} {} blind (all-in)", player, amount, name),
+                }
+            }
+            GameEvent::HoleCardsDealt { hands } => write!(f, "| Cartes distribuées à {} joueurs.", hands.len()),
+            GameEvent::PlayerActed { player, action, amount, all_in } => match action {
+                Action::Fold => write!(f, "| {} se couche.", player),
+                Action::Check => write!(f, "| {} passe.", player),
+                Action::Call if *all_in => write!(f, "| {} suit à tapis avec {}.", player, amount),
+                Action::Call => write!(f, "| {} suit avec {}.", player, amount),
+                Action::Bet(total) => write!(f, "| {} mise {}.", player, total),
+                Action::Raise(total) => write!(f, "| {} relance à {}.", player, total),
+                Action::AllIn => write!(f, "| {} fait tapis avec {}.", player, amount),
+            },
+            GameEvent::StreetDealt { board, .. } => write!(f, "Cartes communes: [{}]", cards_list(board)),
+            GameEvent::HandRevealed { player, cards, equity } => write!(f, "| {} montre {} et {} : équité {:.1}% ({})", player, cards[0], cards[1], equity.equity * 100.0, equity),
+            GameEvent::Showdown { player, cards, rank } => write!(f, "| {} montre {} et {} : {}", player, cards[0], cards[1], rank),
+            GameEvent::UncalledBetReturned { player, amount } => write!(f, "| {} récupère {} jetons non suivis.", player, amount),
+            GameEvent::PotAwarded { player, amount, pot, winners, uncontested } => {
+                let pot_name = if *pot == 0 { "pot principal".to_string() } else { format!("pot secondaire {}", pot) };
+                if *uncontested {
+                    write!(f, "| Tous les autres joueurs se sont couchés. {} remporte {} jetons.", player, amount)
+                } else if *winners > 1 {
+                    write!(f, "| {} partage le {} et reçoit {} jetons", player, pot_name, amount)
+                } else {
+                    write!(f, "| {} remporte le {} ({} jetons)", player, pot_name, amount)
+                }
+            }
+            GameEvent::PlayerEliminated { player, place, prize } => {
+                if *prize > 0 {
+                    write!(f, "| {} est éliminé à la {}e place et remporte {}.", player, place, prize)
+                } else {
+                    write!(f, "| {} est éliminé à la {}e place.", player, place)
+                }
+            }
+        }
+    }
+}
+
+pub trait EventSubscriber { // Reçoit chaque événement de la partie, dans l'ordre
+    fn on_event(&mut self, event: &GameEvent);
+}
+
+impl<F: FnMut(&GameEvent)> EventSubscriber for F {
+    fn on_event(&mut self, event: &GameEvent) {
+        self(event)
+    }
+}
+
+impl EventSubscriber for Sender<GameEvent> { // Transmet les événements à un autre fil (journal, rediffusion, réseau)
+    fn on_event(&mut self, event: &GameEvent) {
+        let _ = self.send(event.clone()); // Un récepteur fermé ne doit pas arrêter la table
+    }
+}
@@ -0,0 +1,171 @@
+use crate::card::Card;
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum HandCategory { // Catégories de mains, de la plus faible à la plus forte
+    HighCard,
+    OnePair,
+    TwoPair,
+    ThreeOfAKind,
+    Straight,
+    Flush,
+    FullHouse,
+    FourOfAKind,
+    StraightFlush,
+}
+
+impl fmt::Display for HandCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            HandCategory::HighCard => "Carte haute",
+            HandCategory::OnePair => "Paire",
+            HandCategory::TwoPair => "Double paire",
+            HandCategory::ThreeOfAKind => "Brelan",
+            HandCategory::Straight => "Quinte",
+            HandCategory::Flush => "Couleur",
+            HandCategory::FullHouse => "Full",
+            HandCategory::FourOfAKind => "Carré",
+            HandCategory::StraightFlush => "Quinte flush",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct HandRank { // Catégorie puis rangs départageants, comparables dans l'ordre lexicographique
+    pub category: HandCategory,
+    pub kickers: [u32; 5],
+}
+
+impl HandRank {
+    fn new(category: HandCategory, ranks: &[u32]) -> Self { // Complète les rangs départageants avec des zéros
+        let mut kickers = [0; 5];
+        for (slot, &rank) in kickers.iter_mut().zip(ranks) {
+            *slot = rank;
+        }
+        Self { category, kickers }
+    }
+}
+
+impl fmt::Display for HandRank {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.category)
+    }
+}
+
+pub fn evaluate_hand(hand: &[Card], community: &[Card]) -> HandRank { // Évalue la meilleure main de cinq cartes parmi les cartes données
+    let mut rank_counts = [0u8; 15];
+    let mut suit_masks = [0u16; 4];
+    let mut rank_mask = 0u16;
+    for card in hand.iter().chain(community) {
+        let rank = card.rank_value();
+        rank_counts[rank as usize] += 1;
+        suit_masks[card.suit as usize] |= 1 << rank;
+        rank_mask |= 1 << rank;
+    }
+
+    if let Some(&flush_mask) = suit_masks.iter().find(|m| m.count_ones() >= 5) {
+        if let Some(high) = straight_high(flush_mask) {
+            return HandRank::new(HandCategory::StraightFlush, &[high]);
+        }
+    }
+
+    let ranks_with = |count: u8| -> Vec<u32> { // Rangs présents au moins `count` fois, du plus fort au plus faible
+        (2..=14).rev().filter(|&r| rank_counts[r as usize] >= count).collect()
+    };
+    let kickers = |excluded: &[u32], count: usize| -> Vec<u32> { // Meilleures cartes restantes
+        (2..=14).rev()
+            .filter(|&r| rank_counts[r as usize] > 0 && !excluded.contains(&r))
+            .take(count)
+            .collect()
+    };
+
+    let quads = ranks_with(4);
+    if let Some(&quad) = quads.first() {
+        let mut ranks = vec![quad];
+        ranks.extend(kickers(&[quad], 1));
+        return HandRank::new(HandCategory::FourOfAKind, &ranks);
+    }
+
+    let trips = ranks_with(3);
+    let pairs = ranks_with(2);
+    if let Some(&trip) = trips.first() {
+        if let Some(&pair) = pairs.iter().find(|&&r| r != trip) {
+            return HandRank::new(HandCategory::FullHouse, &[trip, pair]);
+        }
+    }
+
+    if let Some(&flush_mask) = suit_masks.iter().find(|m| m.count_ones() >= 5) {
+        let ranks: Vec<u32> = (2..=14).rev().filter(|&r| flush_mask & (1 << r) != 0).take(5).collect();
+        return HandRank::new(HandCategory::Flush, &ranks);
+    }
+
+    if let Some(high) = straight_high(rank_mask) {
+        return HandRank::new(HandCategory::Straight, &[high]);
+    }
+
+    if let Some(&trip) = trips.first() {
+        let mut ranks = vec![trip];
+        ranks.extend(kickers(&[trip], 2));
+        return HandRank::new(HandCategory::ThreeOfAKind, &ranks);
+    }
+
+    if pairs.len() >= 2 {
+        let mut ranks = vec![pairs[0], pairs[1]];
+        ranks.extend(kickers(&[pairs[0], pairs[1]], 1));
+        return HandRank::new(HandCategory::TwoPair, &ranks);
+    }
+
+    if let Some(&pair) = pairs.first() {
+        let mut ranks = vec![pair];
+        ranks.extend(kickers(&[pair], 3));
+        return HandRank::new(HandCategory::OnePair, &ranks);
+    }
+
+    HandRank::new(HandCategory::HighCard, &kickers(&[], 5))
+}
+
+fn straight_high(mask: u16) -> Option<u32> { // Carte haute de la meilleure quinte contenue dans un masque de rangs
+    let mask = if mask & (1 << 14) != 0 { mask | 1 << 1 } else { mask }; // L'As compte aussi pour 1
+    (5..=14).rev().find(|&high| {
+        let window = 0b11111 << (high - 4);
+        mask & window == window
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::card::parse_cards;
+
+    fn rank(hand: &str, board: &str) -> HandRank {
+        evaluate_hand(&parse_cards(hand).unwrap(), &parse_cards(board).unwrap())
+    }
+
+    #[test]
+    fn third_pair_only_counts_as_a_kicker() {
+        let board = "Kc Kd Qh Qs 5c";
+        let sevens = rank("7h 7d", board);
+        assert_eq!(sevens, HandRank::new(HandCategory::TwoPair, &[13, 12, 7]));
+        assert!(rank("Ah 2d", board) > sevens); // L'as bat la troisième paire
+        assert!(rank("6h 6d", board) > rank("4h 3d", board)); // La troisième paire sert de kicker, meilleure que le 5 du tableau
+        assert_eq!(rank("4h 3d", board), rank("2h 3c", board)); // Kicker du tableau pour les deux: égalité
+    }
+
+    #[test]
+    fn wheel_is_the_lowest_straight() {
+        let wheel = rank("Ah 2d", "3c 4s 5h Kd 9c");
+        assert_eq!(wheel, HandRank::new(HandCategory::Straight, &[5]));
+        assert!(rank("6h 2d", "3c 4s 5h Kd 9c") > wheel);
+        assert!(wheel > rank("Kh Kc", "3c 4s 5h Kd 9c")); // Mais reste une quinte, au-dessus d'un brelan
+    }
+
+    #[test]
+    fn board_that_plays_ties_whatever_the_hole_cards() {
+        let board = "Ts Js Qs Ks As";
+        assert_eq!(rank("2c 3d", board), rank("9s 8s", board));
+        let straight = "6c 7d 8h 9s Tc";
+        assert_eq!(rank("2c 3d", straight), rank("Ah Kh", straight));
+        assert!(rank("Jh 2d", straight) > rank("2c 3d", straight)); // Une carte qui prolonge la quinte l'emporte
+    }
+}
@@ -0,0 +1,150 @@
+use rand::Rng;
+
+const EXACT_ICM_LIMIT: usize = 10; // Au-delà de ce nombre de joueurs, les places finales sont tirées au hasard
+
+pub fn icm_equities<R: Rng + ?Sized>(stacks: &[u32], prizes: &[u32], iterations: usize, rng: &mut R) -> Vec<f64> { // Gains attendus de chaque joueur selon le modèle ICM (Malmuth-Harville)
+    if stacks.len() <= EXACT_ICM_LIMIT {
+        exact_icm(stacks, prizes)
+    } else {
+        simulate_icm(stacks, prizes, iterations, rng)
+    }
+}
+
+pub fn exact_icm(stacks: &[u32], prizes: &[u32]) -> Vec<f64> { // Parcourt les ensembles de joueurs déjà classés: la probabilité d'une place ne dépend que de cet ensemble
+    let players = stacks.len();
+    let paid = prizes.len().min(players);
+    let total: u64 = stacks.iter().map(|&s| s as u64).sum();
+    let mut equities = vec![0.0; players];
+    if total == 0 || paid == 0 {
+        return equities;
+    }
+    let mut reach = vec![0.0; 1 << players]; // reach[ensemble]: probabilité que ces joueurs occupent exactement les premières places
+    reach[0] = 1.0;
+    for placed in 0usize..1 << players {
+        let place = placed.count_ones() as usize;
+        if place >= paid || reach[placed] == 0.0 {
+            continue;
+        }
+        let remaining = total - (0..players).filter(|&i| placed & (1 << i) != 0).map(|i| stacks[i] as u64).sum::<u64>();
+        if remaining == 0 { // Seuls des tapis vides restent: ils se partagent les places restantes
+            let share = reach[placed] * prizes[place..paid].iter().sum::<u32>() as f64 / (players - place) as f64;
+            for (i, equity) in equities.iter_mut().enumerate() {
+                if placed & (1 << i) == 0 {
+                    *equity += share;
+                }
+            }
+            continue;
+        }
+        for (i, &stack) in stacks.iter().enumerate() {
+            if placed & (1 << i) != 0 || stack == 0 {
+                continue;
+            }
+            let probability = reach[placed] * stack as f64 / remaining as f64;
+            equities[i] += probability * prizes[place] as f64;
+            reach[placed | (1 << i)] += probability;
+        }
+    }
+    equities
+}
+
+pub fn simulate_icm<R: Rng + ?Sized>(stacks: &[u32], prizes: &[u32], iterations: usize, rng: &mut R) -> Vec<f64> { // Tire des classements selon les tapis et fait la moyenne des gains
+    let paid = prizes.len().min(stacks.len());
+    let mut equities = vec![0.0; stacks.len()];
+    if iterations == 0 {
+        return equities;
+    }
+    let mut weights: Vec<u64> = Vec::with_capacity(stacks.len());
+    let mut placed = vec![false; stacks.len()];
+    for _ in 0..iterations {
+        weights.clear();
+        weights.extend(stacks.iter().map(|&s| s as u64));
+        placed.fill(false);
+        let mut remaining: u64 = weights.iter().sum();
+        for (place, &prize) in prizes[..paid].iter().enumerate() {
+            if remaining == 0 { // Seuls des tapis vides restent: ils se partagent les places restantes
+                let share = prizes[place..paid].iter().sum::<u32>() as f64 / (stacks.len() - place) as f64;
+                for (equity, _) in equities.iter_mut().zip(&placed).filter(|(_, &p)| !p) {
+                    *equity += share;
+                }
+                break;
+            }
+            let mut ticket = rng.gen_range(0..remaining);
+            let winner = weights.iter().position(|&w| {
+                if ticket < w {
+                    return true;
+                }
+                ticket -= w;
+                false
+            }).expect("tirage dans la somme des tapis");
+            equities[winner] += prize as f64;
+            remaining -= weights[winner];
+            weights[winner] = 0;
+            placed[winner] = true;
+        }
+    }
+    equities.iter().map(|&e| e / iterations as f64).collect()
+}
+
+pub fn bubble_factor<R: Rng + ?Sized>(stacks: &[u32], prizes: &[u32], hero: usize, villain: usize, iterations: usize, rng: &mut R) -> f64 { // Gains ICM perdus en cas de défaite rapportés aux gains obtenus en cas de victoire, pour un tapis contre `villain`
+    let risk = stacks[hero].min(stacks[villain]);
+    let mut after_win = stacks.to_vec();
+    after_win[hero] += risk;
+    after_win[villain] -= risk;
+    let mut after_loss = stacks.to_vec();
+    after_loss[hero] -= risk;
+    after_loss[villain] += risk;
+    let now = icm_equities(stacks, prizes, iterations, rng)[hero];
+    let gain = icm_equities(&after_win, prizes, iterations, rng)[hero] - now;
+    let loss = now - icm_equities(&after_loss, prizes, iterations, rng)[hero];
+    (loss / gain.max(f64::EPSILON)).clamp(1.0, 10.0) // Vaut 1 sans pression ICM (partie sans gains)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn assert_close(actual: &[f64], expected: &[f64], tolerance: f64) {
+        assert_eq!(actual.len(), expected.len());
+        for (a, e) in actual.iter().zip(expected) {
+            assert!((a - e).abs() <= tolerance, "{:?} au lieu de {:?}", actual, expected);
+        }
+    }
+
+    #[test]
+    fn three_players_match_the_hand_computation() {
+        // Première place: 50%, 30%, 20%. Deuxième pour A: 0,3 x 50/70 + 0,2 x 50/80 = 0,339286;
+        // pour B: 0,5 x 30/50 + 0,2 x 30/80 = 0,375; pour C: 0,5 x 20/50 + 0,3 x 20/70 = 0,285714
+        let equities = exact_icm(&[50, 30, 20], &[70, 30]);
+        assert_close(&equities, &[35.0 + 30.0 * (3.0 / 14.0 + 0.125), 21.0 + 30.0 * 0.375, 14.0 + 30.0 * (0.2 + 3.0 / 35.0)], 1e-9);
+        assert_close(&equities, &[45.18, 32.25, 22.57], 0.01);
+    }
+
+    #[test]
+    fn equities_add_up_to_the_prizes_paid() {
+        for (stacks, prizes) in [
+            (vec![1000, 500, 250, 250, 100], vec![500, 300, 200]),
+            (vec![3000, 0, 0, 1500], vec![600, 300, 100]), // Tapis vides: ils se partagent les dernières places
+            (vec![10, 20], vec![65, 35, 10]), // Plus de places payées que de joueurs
+        ] {
+            let paid: u32 = prizes.iter().take(stacks.len()).sum();
+            let total: f64 = exact_icm(&stacks, &prizes).iter().sum();
+            assert!((total - paid as f64).abs() < 1e-9, "{:?} {:?}: {}", stacks, prizes, total);
+        }
+    }
+
+    #[test]
+    fn simulation_agrees_with_exact_computation() {
+        let (stacks, prizes) = ([4000, 2500, 1500, 1200, 800], [500, 300, 200]);
+        let simulated = simulate_icm(&stacks, &prizes, 200_000, &mut StdRng::seed_from_u64(5));
+        assert_close(&simulated, &exact_icm(&stacks, &prizes), 5.0); // 0,5% de la dotation
+    }
+
+    #[test]
+    fn no_bubble_pressure_without_prizes() {
+        let mut rng = StdRng::seed_from_u64(1);
+        assert_eq!(bubble_factor(&[1000, 600, 400], &[], 1, 0, 1000, &mut rng), 1.0);
+        assert!(bubble_factor(&[1000, 600, 400], &[700, 300], 1, 0, 1000, &mut rng) > 1.0); // Deux payés sur trois: perdre coûte plus que gagner ne rapporte
+    }
+}
@@ -0,0 +1,85 @@
+use std::collections::VecDeque;
+use std::io::{BufRead, Write};
+
+const CLEAR_SCREEN: &str = "\x1B[2J\x1B[1;1H"; // Séquence ANSI qui efface le terminal
+
+pub trait InputProvider { // Source de lignes saisies: clavier, script, connexion réseau
+    fn read_line(&mut self) -> Option<String>; // Ligne sans les espaces autour; None en fin d'entrée
+}
+
+pub trait OutputSink { // Destination des messages: terminal, fichier, connexion réseau
+    fn line(&mut self, message: &str);
+
+    fn clear(&mut self) {} // Efface l'écran avant et après l'affichage de cartes privées
+}
+
+pub struct StdinInput; // Clavier du terminal
+
+impl InputProvider for StdinInput {
+    fn read_line(&mut self) -> Option<String> {
+        let mut input = String::new();
+        match std::io::stdin().read_line(&mut input) {
+            Ok(0) | Err(_) => None,
+            Ok(_) => Some(input.trim().to_string()),
+        }
+    }
+}
+
+pub struct ReaderInput<R>(pub R); // N'importe quel lecteur ligne à ligne, par exemple un BufReader sur une TcpStream
+
+impl<R: BufRead> InputProvider for ReaderInput<R> {
+    fn read_line(&mut self) -> Option<String> {
+        let mut input = String::new();
+        match self.0.read_line(&mut input) {
+            Ok(0) | Err(_) => None,
+            Ok(_) => Some(input.trim().to_string()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ScriptedInput { // Lignes fixées à l'avance, pour les tests et les parties rejouées
+    lines: VecDeque<String>,
+}
+
+impl ScriptedInput {
+    pub fn new<I, S>(lines: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self { lines: lines.into_iter().map(Into::into).collect() }
+    }
+}
+
+impl InputProvider for ScriptedInput {
+    fn read_line(&mut self) -> Option<String> {
+        self.lines.pop_front().map(|line| line.trim().to_string())
+    }
+}
+
+pub struct TerminalOutput; // Sortie standard, écran effacé par séquence ANSI
+
+impl OutputSink for TerminalOutput {
+    fn line(&mut self, message: &str) {
+        println!("{}", message);
+    }
+
+    fn clear(&mut self) {
+        print!("{}", CLEAR_SCREEN);
+    }
+}
+
+pub struct WriterOutput<W>(pub W); // N'importe quel flux: fichier, TcpStream, tampon en mémoire
+
+impl<W: Write> OutputSink for WriterOutput<W> {
+    fn line(&mut self, message: &str) {
+        let _ = writeln!(self.0, "{}", message); // Un client déconnecté ne doit pas arrêter la table
+    }
+}
+
+pub struct SilentOutput; // Ignore tous les messages (simulations, IA entre elles)
+
+impl OutputSink for SilentOutput {
+    fn line(&mut self, _message: &str) {}
+}
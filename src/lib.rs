@@ -0,0 +1,32 @@
+//! Moteur de poker Texas Hold'em: cartes, évaluation des mains, équité, règles de mise et déroulement des parties.
+//! Le jeu en terminal (`main.rs`) n'est qu'une interface parmi d'autres au-dessus de cette bibliothèque.
+
+pub mod agent;
+pub mod betting;
+pub mod blinds;
+pub mod card;
+pub mod cash_game;
+pub mod deck;
+pub mod equity;
+pub mod evaluator;
+pub mod event;
+pub mod hand;
+pub mod icm;
+pub mod io;
+pub mod player;
+pub mod poker_game;
+pub mod pot;
+pub mod strategy;
+pub mod table;
+pub mod tournament;
+
+pub use agent::{PlayerAgent, PlayerView};
+pub use card::{parse_cards, Card, ParseCardError, Rank, Suit};
+pub use equity::{calculate_equity, calculate_equity_vs_ranges, exact_equity, Equity, Range};
+pub use evaluator::{evaluate, evaluate_cards, CardSet, HandValue};
+pub use event::{EventSubscriber, GameEvent};
+pub use hand::{evaluate_hand, HandCategory, HandRank};
+pub use io::{InputProvider, OutputSink};
+pub use player::Player;
+pub use poker_game::PokerGame;
+pub use table::{ConfigError, TableConfig};
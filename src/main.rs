@@ -1,57 +1,175 @@
-mod poker_game;
-mod card;
-mod player;
+mod config;
 
-use poker_game::PokerGame;
-use player::Player;
-use std::io;
+use config::{load_schedule, CommandLine, GameSetup, Mode, Structure, USAGE};
+use poker_game::cash_game::GameMode;
+use poker_game::io::{InputProvider, StdinInput};
+use poker_game::strategy::Difficulty;
+use poker_game::tournament::PayoutTable;
+use poker_game::{Player, PokerGame, TableConfig};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+fn ask(interactive: bool, question: &str) -> Option<String> { // Pose une question au clavier; None sans questions (--config, --defaults) ou en fin d'entrée
+    if !interactive {
+        return None;
+    }
+    println!("{}", question);
+    StdinInput.read_line()
+}
 
 fn main() {
+    let (setup, interactive) = match GameSetup::from_args(std::env::args().skip(1)) {
+        Ok(CommandLine::Help) => {
+            println!("{}", USAGE);
+            return;
+        }
+        Ok(CommandLine::Play { setup, interactive }) => (setup, interactive),
+        Err(error) => {
+            eprintln!("Erreur: {}.", error);
+            eprintln!("{}", USAGE);
+            std::process::exit(2);
+        }
+    };
     let mut players = Vec::new();
 
     println!("+==================== Configuration des joueurs ====================+");
-    println!("| Entrez le nombre de joueurs humains: ");
-    let mut input = String::new();
-    io::stdin().read_line(&mut input).unwrap();
-    let num_human_players: usize = input.trim().parse().unwrap_or_else(|_| {
-        eprintln!("Entrée non valide, nombre de joueurs humains par défaut: 1");
-        1
+    let names = match setup.humans {
+        Some(names) => names,
+        None if setup.ai.is_some() => Vec::new(), // Seules les IA sont données: partie entre IA
+        None => {
+            let count = match ask(interactive, "| Entrez le nombre de joueurs humains: ") {
+                Some(count) => count.parse().unwrap_or_else(|_| {
+                    eprintln!("Entrée non valide, nombre de joueurs humains par défaut: 1");
+                    1
+                }),
+                None => 1,
+            };
+            (0..count)
+                .map(|i| ask(interactive, &format!("| Entrez le nom du joueur humain {}: ", i + 1))
+                    .filter(|name| !name.is_empty())
+                    .unwrap_or_else(|| format!("Joueur {}", i + 1)))
+                .collect()
+        }
+    };
+    for name in names {
+        players.push(Player::new(name, true));
+    }
+
+    let difficulties = match setup.ai {
+        Some(difficulties) => difficulties,
+        None => {
+            let count = ask(interactive, "| Entrez le nombre de joueurs IA: ").and_then(|count| count.parse().ok()).unwrap_or(1);
+            (0..count).map(|i| loop {
+                let question = format!("| Choisissez le niveau de difficulté pour l'IA {}: (1) Facile, (2) Intermédiaire, (3) Difficile, (4) Extrêmement Difficile", i + 1);
+                let Some(input) = ask(interactive, &question) else {
+                    break Difficulty::default();
+                };
+                match input.parse::<u32>().ok().and_then(Difficulty::from_level) {
+                    Some(difficulty) => break difficulty,
+                    None => println!("| Erreur: Veuillez entrer un niveau de difficulté valide (1 à 4)"),
+                }
+            }).collect()
+        }
+    };
+    for (i, difficulty) in difficulties.into_iter().enumerate() {
+        let ai_name = format!("IA-{}-{}", i + 1, difficulty as u32 + 1);
+        players.push(Player::new_ai(ai_name, difficulty));
+    }
+
+    let mut config = TableConfig::default();
+    if setup.starting_stack.is_none() && setup.small_blind.is_none() && setup.big_blind.is_none() {
+        let question = format!("| Entrez le tapis de départ et les blinds, par exemple '1000 5/10' (Entrée pour {} {}/{}): ", config.starting_stack, config.small_blind, config.big_blind);
+        let input = ask(interactive, &question).unwrap_or_default();
+        let mut words = input.split_whitespace();
+        if let Some(stack) = words.next().and_then(|w| w.parse().ok()) {
+            config.starting_stack = stack;
+        }
+        if let Some((small_blind, big_blind)) = words.next().and_then(|w| w.split_once('/')) {
+            config.small_blind = small_blind.parse().unwrap_or(config.small_blind);
+            config.big_blind = big_blind.parse().unwrap_or(config.big_blind);
+        }
+    }
+    config.starting_stack = setup.starting_stack.unwrap_or(config.starting_stack);
+    config.small_blind = setup.small_blind.unwrap_or(config.small_blind);
+    config.big_blind = setup.big_blind.unwrap_or(config.big_blind);
+    config.ante = setup.ante.unwrap_or(config.ante);
+    config.big_blind_ante = setup.big_blind_ante.unwrap_or(config.big_blind_ante);
+    config.straddle = setup.straddle.unwrap_or(config.straddle);
+    config.max_seats = setup.max_seats.unwrap_or(config.max_seats);
+
+    let structure = setup.structure.unwrap_or_else(|| {
+        match ask(interactive, "| Choisissez la structure de mise: (1) No-limit, (2) Pot-limit, (3) Limite fixe").as_deref() {
+            Some("2") => Structure::PotLimit,
+            Some("3") => Structure::FixedLimit,
+            _ => Structure::NoLimit,
+        }
     });
+    config.betting_structure = structure.betting_structure(config.big_blind);
 
-    for i in 0..num_human_players {
-        println!("| Entrez le nom du joueur humain {}: ", i + 1);
-        let mut name = String::new();
-        io::stdin().read_line(&mut name).unwrap();
-        players.push(Player::new(name.trim().to_string(), true));
+    let mode = setup.mode.unwrap_or_else(|| {
+        match ask(interactive, "| Choisissez le mode de jeu: (1) Tournoi, (2) Partie cash").as_deref() {
+            Some("2") => Mode::Cash,
+            _ => Mode::Tournament,
+        }
+    });
+    if mode == Mode::Cash {
+        let max_buy_in = setup.max_buy_in.unwrap_or_else(|| {
+            ask(interactive, &format!("| Entrez la cave maximale (Entrée pour {}): ", config.starting_stack))
+                .and_then(|input| input.parse().ok())
+                .unwrap_or(config.starting_stack)
+        });
+        config.mode = GameMode::Cash { max_buy_in };
     }
 
-    println!("| Entrez le nombre de joueurs IA: ");
-    input.clear();
-    io::stdin().read_line(&mut input).unwrap();
-    let num_ai_players: usize = input.trim().parse().unwrap_or(1);
-
-    for i in 0..num_ai_players {
-        let difficulty = loop {
-            println!("| Choisissez le niveau de difficulté pour l'IA {}: (1) Facile, (2) Intermédiaire, (3) Difficile, (4) Extrêmement Difficile", i + 1);
-            input.clear();
-            io::stdin().read_line(&mut input).unwrap();
-            
-            match input.trim().parse::<u32>() {
-                Ok(level) if level >= 1 && level <= 4 => break level,
-                _ => {
-                    println!("| Erreur: Veuillez entrer un niveau de difficulté valide (1 à 4)");
-                    continue;
-                }
+    let rng = setup.seed.map_or_else(StdRng::from_entropy, StdRng::seed_from_u64);
+    let mut game = match PokerGame::with_config(players, config, rng) {
+        Ok(game) => game,
+        Err(error) => {
+            eprintln!("Configuration invalide: {}.", error);
+            std::process::exit(1);
+        }
+    };
+    if config.mode == GameMode::Tournament {
+        let choice = setup.blind_schedule.unwrap_or_else(|| {
+            let question = format!("| Choisissez la structure des blinds: (1) Fixes {}/{}, (2) Turbo, (3) Régulière, (4) Deep, ou le chemin d'un fichier de niveaux", game.small_blind, game.big_blind);
+            ask(interactive, &question).unwrap_or_default()
+        });
+        game.blind_schedule = match load_schedule(&choice) {
+            Ok(schedule) => schedule,
+            Err(error) => {
+                eprintln!("Structure ignorée ({}), blinds fixes {}/{}", error, game.small_blind, game.big_blind);
+                None
             }
         };
-    
-        let ai_name = format!("IA-{}-{}", i + 1, difficulty);
-        players.push(Player::new(ai_name, false));
+
+        if let Some(prize_pool) = setup.prize_pool {
+            game.prize_pool = prize_pool;
+            game.payouts = setup.payouts.unwrap_or_default();
+        } else {
+            let input = ask(interactive, "| Dotation du tournoi et répartition, par exemple '1000 50/30/20' (Entrée pour jouer sans gains): ").unwrap_or_default();
+            let mut words = input.split_whitespace();
+            if let Some(prize_pool) = words.next() {
+                let payouts = match words.next() {
+                    Some(payouts) => payouts.parse::<PayoutTable>(),
+                    None => Ok(setup.payouts.unwrap_or_default()),
+                };
+                match (prize_pool.parse::<u32>(), payouts) {
+                    (Ok(prize_pool), Ok(payouts)) => {
+                        game.prize_pool = prize_pool;
+                        game.payouts = payouts;
+                    }
+                    (Err(_), _) => eprintln!("Dotation invalide, partie sans gains"),
+                    (_, Err(error)) => eprintln!("Répartition ignorée ({}), partie sans gains", error),
+                }
+            }
+        }
     }
 
     println!("+==================== Initialisation du jeu ====================+");
-    println!("| Initialisation du jeu avec {} joueurs.", players.len());
-    let mut game = PokerGame::new(players);
+    println!("| Initialisation du jeu avec {} joueurs: {} {}, {}.", game.players.len(), game.variant, game.betting_structure, game.mode);
+    if let Some(seed) = setup.seed {
+        println!("| Graine du mélange: {} (relancez avec --seed {} pour rejouer les mêmes donnes).", seed, seed);
+    }
     game.run();
     println!("+==============================================================+");
 }
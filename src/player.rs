@@ -1,24 +1,47 @@
 use crate::card::Card;
+use crate::strategy::Difficulty;
+use crate::table::DEFAULT_STARTING_STACK;
 
 #[derive(Debug, Clone)]
 pub struct Player { 
     pub name: String,
+    pub seat: usize, // Numéro de siège, inchangé pendant toute la partie
     pub chips: u32,
     pub hand: Vec<Card>,
     pub is_human: bool,
+    pub difficulty: Option<Difficulty>, // Niveau de l'IA, None pour un humain
     pub is_active: bool,
     pub current_bet: u32,
+    pub total_bet: u32, // Total misé par le joueur depuis le début de la main
+    pub buy_in: u32, // Total des caves en partie cash
+    pub missed_big_blind: bool, // Absent au passage de la grosse blind: il la paiera à son retour
 }
 
 impl Player {
     pub fn new(name: String, is_human: bool) -> Self {
         Self {
             name,
-            chips: 100,
+            seat: 0,
+            chips: DEFAULT_STARTING_STACK,
             hand: Vec::new(),
             is_human,
+            difficulty: None,
             is_active: true,
             current_bet: 0,
+            total_bet: 0,
+            buy_in: DEFAULT_STARTING_STACK,
+            missed_big_blind: false,
         }
     }
+
+    pub fn new_ai(name: String, difficulty: Difficulty) -> Self { // Crée un joueur IA avec le niveau de difficulté donné
+        Self {
+            difficulty: Some(difficulty),
+            ..Self::new(name, false)
+        }
+    }
+
+    pub fn is_all_in(&self) -> bool { // Vrai si le joueur est toujours en jeu mais n'a plus de jetons
+        self.is_active && self.chips == 0
+    }
 }
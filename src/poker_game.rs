@@ -1,444 +1,1096 @@
 //use crate::card::Card;
-use crate::card::{Card, Suit};
+use crate::agent::{default_agent, PlayerAgent, PlayerView};
+use crate::cash_game::{CashGameError, GameMode, SessionResult, TableCommand};
+use crate::blinds::BlindSchedule;
+use crate::betting::{Action, BettingRound, BettingStructure, LegalActions};
+use crate::card::Card;
+use crate::deck::Deck;
+use crate::equity::{calculate_equity, calculate_equity_vs_ranges, Equity, Range};
+use crate::event::{BlindKind, EventSubscriber, GameEvent};
+use crate::evaluator::{evaluate_cards, HandValue};
+use crate::icm::{bubble_factor, icm_equities};
+use crate::io::{InputProvider, OutputSink, StdinInput, TerminalOutput};
 use crate::player::Player;
-use rand::seq::SliceRandom;
-use rand::Rng;
-use std::collections::HashMap;
+use crate::pot::{build_pots, return_uncalled_bet, Pot};
+use crate::tournament::{rank_eliminations, Finish, PayoutTable};
+use crate::table::{ConfigError, GameVariant, TableConfig};
+use crate::strategy::{DecisionContext, Difficulty, Position};
+use rand::rngs::StdRng;
+use rand::{RngCore, SeedableRng};
+use std::collections::{HashMap, VecDeque};
 
+const EQUITY_ITERATIONS: usize = 1000; // Nombre de tableaux simulés pour estimer l'équité d'une main
+const ICM_ITERATIONS: usize = 2000; // Classements tirés pour l'ICM au-delà de dix joueurs
+
+macro_rules! say { // Comme println!, mais vers la sortie de la partie
+    ($game:expr, $($arg:tt)*) => {
+        $game.output.line(&format!($($arg)*))
+    };
+}
 
 pub struct PokerGame { // Structure de données pour une partie de poker
-    pub deck: Vec<Card>,
+    pub deck: Deck,
     pub players: Vec<Player>,
     pub pot: u32,
     pub community_cards: Vec<Card>,
-    pub current_bet: u32,
+    pub betting: BettingRound, // Mise à égaler, relance minimale et droit de relancer pour la rue en cours
     pub last_bettor: Option<usize>,
     pub small_blind: u32,
     pub big_blind: u32,
-    pub dealer_position: usize,
+    pub ante: u32, // Ante payé par chaque joueur, ou ante unique payé par la grosse blind si big_blind_ante (0: pas d'ante)
+    pub big_blind_ante: bool,
+    pub straddle: bool, // Le joueur après la grosse blind poste un straddle de deux grosses blinds (à partir de trois joueurs)
+    pub blind_schedule: Option<BlindSchedule>, // Structure de tournoi: fixe les blinds et l'ante à chaque niveau (None: blinds fixes)
+    pub prize_pool: u32, // Dotation du tournoi (0: pas de gains)
+    pub payouts: PayoutTable,
+    pub finishes: Vec<Finish>, // Joueurs éliminés, dans l'ordre d'élimination, puis le vainqueur
+    pub mode: GameMode,
+    pub max_seats: usize, // Nombre de sièges de la table, pour les nouveaux arrivants en partie cash
+    pub sitting_out: Vec<Player>, // Joueurs assis mais absents des mains (pause, ou sans jetons en partie cash)
+    pub departed: Vec<SessionResult>, // Joueurs partis en cours de session
+    straddler: Option<usize>, // Joueur qui a posté le straddle dans la main en cours
+    pub button_seat: usize, // Siège du bouton, éventuellement vide (bouton mort)
+    pub small_blind_seat: usize, // Siège de la petite blind, éventuellement vide (petite blind morte)
+    pub big_blind_seat: Option<usize>, // Siège de la grosse blind, None avant la première main
+    pub betting_structure: BettingStructure,
+    pub variant: GameVariant,
+    pub output: Box<dyn OutputSink>, // Messages publics de la table (terminal par défaut)
+    pub commands: Box<dyn InputProvider>, // Commandes entre deux mains d'une partie cash (clavier par défaut)
+    subscribers: Vec<Box<dyn EventSubscriber>>, // Reçoivent les événements de la partie en plus de leur affichage sur `output`
+    agents: HashMap<String, Box<dyn PlayerAgent>>, // Décideur de chaque joueur, par nom; à défaut, voir default_agent
+    pub input_closed: bool, // Un agent n'a plus d'entrée: la partie s'arrête à la fin de la main en cours
+    deck_rng: Box<dyn RngCore>, // Générateur réservé au mélange: la même graine donne toujours les mêmes donnes, quoi que jouent les joueurs
+    rng: StdRng, // Simulations d'équité et d'ICM, décisions des IA; tiré du générateur du paquet à la création
 }
 
 impl PokerGame { 
     pub fn new(players: Vec<Player>) -> Self { // Crée une nouvelle partie de poker avec des joueurs donnés
+        Self::with_rng(players, StdRng::from_entropy())
+    }
+
+    pub fn with_seed(players: Vec<Player>, seed: u64) -> Self { // Crée une partie reproductible: la même graine donne toujours la même donne
+        Self::with_rng(players, StdRng::seed_from_u64(seed))
+    }
+
+    pub fn with_rng<R: RngCore + 'static>(players: Vec<Player>, rng: R) -> Self { // Crée une partie qui mélange avec le générateur fourni, avec les réglages par défaut et les tapis des joueurs
+        let config = TableConfig { max_seats: players.len().max(9), ..TableConfig::default() };
+        Self::build(players, config, rng)
+    }
+
+    pub fn with_config<R: RngCore + 'static>(players: Vec<Player>, config: TableConfig, rng: R) -> Result<Self, ConfigError> { // Crée une partie après avoir vérifié les réglages; chaque joueur reçoit le tapis de départ
+        config.validate(players.len())?;
+        let mut players = players;
+        for player in &mut players {
+            player.chips = config.starting_stack;
+        }
+        Ok(Self::build(players, config, rng))
+    }
+
+    fn build<R: RngCore + 'static>(players: Vec<Player>, config: TableConfig, mut deck_rng: R) -> Self {
+        let rng = StdRng::seed_from_u64(deck_rng.next_u64());
+        let mut players = players;
+        for (seat, player) in players.iter_mut().enumerate() {
+            player.seat = seat;
+            player.buy_in = player.chips;
+        }
         let mut game = Self {
-            deck: Vec::new(),
+            deck: Deck::new(),
             players,
             pot: 0,
             community_cards: Vec::new(),
-            current_bet: 0,
+            betting: BettingRound::new(0, 0, 0, BettingStructure::NoLimit),
             last_bettor: None,
-            small_blind: 5,
-            big_blind: 10,
-            dealer_position: 0,
+            small_blind: config.small_blind,
+            big_blind: config.big_blind,
+            ante: config.ante,
+            big_blind_ante: config.big_blind_ante,
+            straddle: config.straddle,
+            blind_schedule: None,
+            prize_pool: 0,
+            payouts: PayoutTable::default(),
+            finishes: Vec::new(),
+            mode: config.mode,
+            max_seats: config.max_seats,
+            sitting_out: Vec::new(),
+            departed: Vec::new(),
+            straddler: None,
+            button_seat: 0,
+            small_blind_seat: 0,
+            big_blind_seat: None,
+            betting_structure: config.betting_structure,
+            variant: config.variant,
+            output: Box::new(TerminalOutput),
+            commands: Box::new(StdinInput),
+            subscribers: Vec::new(),
+            agents: HashMap::new(),
+            input_closed: false,
+            deck_rng: Box::new(deck_rng),
+            rng,
         };
         game.reset_deck(); // Initialisation du paquet de cartes
         game
     }
 
-    fn reset_deck(&mut self) { // Réinitialise le paquet de cartes pour un nouveau tour
-        let suits: Vec<Suit> = vec![Suit::Hearts, Suit::Spades, Suit::Diamonds, Suit::Clubs];
-        let ranks = vec!["2", "3", "4", "5", "6", "7", "8", "9", "10", "J", "Q", "K", "A"];
-        self.deck.clear();
-        for suit in &suits {
-            for rank in &ranks {
-                self.deck.push(Card {
-                    rank: rank.to_string(),
-                    suit: suit.clone(),
-                });
-            }
+    pub fn set_agent(&mut self, name: &str, agent: Box<dyn PlayerAgent>) { // Confie les décisions d'un joueur à un agent (réseau, script, robot...)
+        self.agents.insert(name.to_string(), agent);
+    }
+
+    pub fn subscribe(&mut self, subscriber: Box<dyn EventSubscriber>) { // Abonne un afficheur, un journal, un compteur de statistiques...
+        self.subscribers.push(subscriber);
+    }
+
+    fn emit(&mut self, event: GameEvent) { // Affiche l'événement sur la sortie de la partie puis le transmet aux abonnés
+        say!(self, "{}", event);
+        for subscriber in &mut self.subscribers {
+            subscriber.on_event(&event);
         }
-        self.deck.shuffle(&mut rand::thread_rng()); // Mélange du paquet
+    }
+
+    fn reset_deck(&mut self) { // Réinitialise le paquet de cartes pour un nouveau tour
+        self.deck = Deck::shuffled(&mut self.deck_rng);
     }
 
     pub fn run(&mut self) {  // Lancement de la partie
-        println!("Début du jeu avec {} joueurs.", self.players.len());
+        if let GameMode::Cash { .. } = self.mode {
+            self.run_cash_game();
+            return;
+        }
+        say!(self, "Début du jeu avec {} joueurs.", self.players.len());
         while self.players.len() > 1 {
+            if self.input_closed {
+                self.show_interrupted();
+                return;
+            }
+            self.new_round();
+        }
+        say!(self, "Le gagnant est {} avec {} jetons!", self.players[0].name, self.players[0].chips);
+        let prize = self.payouts.prizes(self.prize_pool, self.entrants()).first().copied().unwrap_or(0);
+        self.finishes.push(Finish { place: 1, name: self.players[0].name.clone(), prize });
+        self.show_standings();
+    }
+
+    fn run_cash_game(&mut self) { // Partie cash: les mains s'enchaînent jusqu'à la fin de session demandée
+        say!(self, "Début de la partie cash avec {} joueurs.", self.players.len());
+        while !self.input_closed && self.between_hands() {
+            if self.players.len() < 2 {
+                say!(self, "| Il faut au moins deux joueurs actifs pour distribuer une main.");
+                continue;
+            }
             self.new_round();
-            self.advance_dealer();
         }
-        println!("Le gagnant est {} avec {} jetons!", self.players[0].name, self.players[0].chips);
+        self.show_session_summary();
+    }
+
+    fn between_hands(&mut self) -> bool { // Montre la table et applique les commandes jusqu'à une ligne vide; faux en fin de session
+        say!(self, "+==================== Entre deux mains ====================+");
+        let mut seated: Vec<(&Player, bool)> = self.players.iter().map(|p| (p, false)).chain(self.sitting_out.iter().map(|p| (p, true))).collect();
+        seated.sort_by_key(|(p, _)| p.seat);
+        for (player, absent) in seated {
+            say!(self, "| Siège {}: {} ({} jetons){}", player.seat + 1, player.name, player.chips, if absent { " - absent" } else { "" });
+        }
+        say!(self, "| Entrée pour continuer, ou: recave <montant> <nom>, pause <nom>, retour <nom>, quitter <nom>, rejoindre <montant> <nom>, rejoindre <montant> ia <niveau>, fin");
+        loop {
+            let Some(input) = self.commands.read_line() else { // Fin de l'entrée standard: la session s'arrête
+                return false;
+            };
+            let command = match input.parse::<TableCommand>() {
+                Ok(command) => command,
+                Err(reason) => {
+                    say!(self, "| Commande invalide: {}.", reason);
+                    continue;
+                }
+            };
+            let result = match command {
+                TableCommand::Continue => return true,
+                TableCommand::End => return false,
+                TableCommand::Rebuy { name, amount } => self.rebuy(&name, amount).map(|()| format!("{} se recave de {}.", name, amount)),
+                TableCommand::SitOut(name) => self.sit_out(&name).map(|()| format!("{} fait une pause.", name)),
+                TableCommand::SitIn(name) => self.sit_in(&name).map(|()| format!("{} revient à la table.", name)),
+                TableCommand::Leave(name) => self.leave(&name).map(|result| format!("{} quitte la table. {}", name, result)),
+                TableCommand::JoinHuman { name, amount } => {
+                    let player = Player { chips: amount, ..Player::new(name.clone(), true) };
+                    self.join(player).map(|seat| format!("{} s'assoit au siège {}.", name, seat + 1))
+                }
+                TableCommand::JoinAi { difficulty, amount } => self.join_ai(difficulty, amount),
+            };
+            match result {
+                Ok(message) => say!(self, "| {}", message),
+                Err(reason) => say!(self, "| Commande refusée: {}.", reason),
+            }
+        }
+    }
+
+    fn show_interrupted(&mut self) { // Tournoi arrêté avant la fin: tapis de chacun au moment de l'arrêt
+        say!(self, "+==================== Partie interrompue (fin de l'entrée) ====================+");
+        for i in 0..self.players.len() {
+            say!(self, "| {}: {} jetons", self.players[i].name, self.players[i].chips);
+        }
+    }
+
+    fn max_buy_in(&self) -> Result<u32, CashGameError> {
+        match self.mode {
+            GameMode::Cash { max_buy_in } => Ok(max_buy_in),
+            GameMode::Tournament => Err(CashGameError::NotCashGame),
+        }
+    }
+
+    fn seated_player_mut(&mut self, name: &str) -> Result<&mut Player, CashGameError> { // Joueur en jeu ou absent, par son nom
+        self.players.iter_mut()
+            .chain(self.sitting_out.iter_mut())
+            .find(|p| p.name == name)
+            .ok_or_else(|| CashGameError::UnknownPlayer(name.to_string()))
+    }
+
+    pub fn rebuy(&mut self, name: &str, amount: u32) -> Result<(), CashGameError> { // Recave entre deux mains, sans dépasser la cave maximale; un joueur sans jetons revient en jeu
+        let max_buy_in = self.max_buy_in()?;
+        let player = self.seated_player_mut(name)?;
+        if amount == 0 {
+            return Err(CashGameError::ZeroBuyIn);
+        }
+        if player.chips + amount > max_buy_in {
+            return Err(CashGameError::AboveMaxBuyIn { max_buy_in });
+        }
+        let busted = player.chips == 0;
+        player.chips += amount;
+        player.buy_in += amount;
+        if busted && self.sitting_out.iter().any(|p| p.name == name) {
+            self.sit_in(name)?;
+        }
+        Ok(())
+    }
+
+    pub fn sit_out(&mut self, name: &str) -> Result<(), CashGameError> { // Le joueur garde son siège mais ne reçoit plus de cartes
+        self.max_buy_in()?;
+        let index = self.players.iter().position(|p| p.name == name).ok_or_else(|| CashGameError::UnknownPlayer(name.to_string()))?;
+        let player = self.players.remove(index);
+        self.sitting_out.push(player);
+        Ok(())
+    }
+
+    pub fn sit_in(&mut self, name: &str) -> Result<(), CashGameError> {
+        let index = self.sitting_out.iter().position(|p| p.name == name).ok_or_else(|| CashGameError::UnknownPlayer(name.to_string()))?;
+        if self.sitting_out[index].chips == 0 {
+            return Err(CashGameError::NoChips(name.to_string()));
+        }
+        let player = self.sitting_out.remove(index);
+        self.seat_player(player);
+        Ok(())
+    }
+
+    pub fn leave(&mut self, name: &str) -> Result<SessionResult, CashGameError> { // Libère le siège et enregistre le bilan du joueur
+        self.max_buy_in()?;
+        let player = match self.players.iter().position(|p| p.name == name) {
+            Some(index) => self.players.remove(index),
+            None => {
+                let index = self.sitting_out.iter().position(|p| p.name == name).ok_or_else(|| CashGameError::UnknownPlayer(name.to_string()))?;
+                self.sitting_out.remove(index)
+            }
+        };
+        let result = SessionResult { name: player.name, buy_in: player.buy_in, cash_out: player.chips };
+        self.departed.push(result.clone());
+        Ok(result)
+    }
+
+    pub fn join(&mut self, mut player: Player) -> Result<usize, CashGameError> { // Assoit un nouveau joueur au premier siège libre; renvoie ce siège
+        let max_buy_in = self.max_buy_in()?;
+        if self.players.iter().chain(&self.sitting_out).any(|p| p.name == player.name) {
+            return Err(CashGameError::NameTaken(player.name));
+        }
+        if player.chips == 0 {
+            return Err(CashGameError::ZeroBuyIn);
+        }
+        if player.chips > max_buy_in {
+            return Err(CashGameError::AboveMaxBuyIn { max_buy_in });
+        }
+        let seat = (0..self.max_seats)
+            .find(|&seat| self.players.iter().chain(&self.sitting_out).all(|p| p.seat != seat))
+            .ok_or(CashGameError::TableFull)?;
+        player.seat = seat;
+        player.buy_in = player.chips;
+        self.seat_player(player);
+        Ok(seat)
+    }
+
+    fn join_ai(&mut self, difficulty: Difficulty, amount: u32) -> Result<String, CashGameError> {
+        let number = (1..).find(|n| self.players.iter().chain(&self.sitting_out).all(|p| !p.name.starts_with(&format!("IA-{}-", n)))).unwrap_or(1);
+        let name = format!("IA-{}-{}", number, difficulty as u32 + 1);
+        let player = Player { chips: amount, ..Player::new_ai(name.clone(), difficulty) };
+        self.join(player).map(|seat| format!("{} s'assoit au siège {}.", name, seat + 1))
+    }
+
+    fn seat_player(&mut self, player: Player) { // Les joueurs en jeu restent rangés par numéro de siège
+        let index = self.players.iter().position(|p| p.seat > player.seat).unwrap_or(self.players.len());
+        self.players.insert(index, player);
+    }
+
+    fn bench_busted_players(&mut self) { // Partie cash: les IA sans jetons se recavent, les humains attendent une recave
+        let Ok(max_buy_in) = self.max_buy_in() else {
+            return;
+        };
+        for player in self.players.iter_mut().filter(|p| p.chips == 0 && !p.is_human) {
+            player.chips = max_buy_in;
+            player.buy_in += max_buy_in;
+            say!(self, "| {} se recave de {}.", player.name, max_buy_in);
+        }
+        while let Some(index) = self.players.iter().position(|p| p.chips == 0) {
+            let player = self.players.remove(index);
+            say!(self, "| {} n'a plus de jetons: recave possible jusqu'à {}.", player.name, max_buy_in);
+            self.sitting_out.push(player);
+        }
+    }
+
+    pub fn session_summary(&self) -> Vec<SessionResult> { // Bilan de chaque joueur de la session, partis compris
+        let mut summary = self.departed.clone();
+        summary.extend(self.players.iter().chain(&self.sitting_out).map(|p| SessionResult { name: p.name.clone(), buy_in: p.buy_in, cash_out: p.chips }));
+        summary
+    }
+
+    fn show_session_summary(&mut self) {
+        say!(self, "+==================== Bilan de la session ====================+");
+        for result in self.session_summary() {
+            say!(self, "| {}", result);
+        }
+    }
+
+    fn entrants(&self) -> usize { // Nombre de joueurs au départ du tournoi
+        self.players.len() + self.finishes.len()
+    }
+
+    pub fn standings(&self) -> Vec<Finish> { // Classement final, du vainqueur au premier éliminé
+        let mut standings = self.finishes.clone();
+        standings.sort_by_key(|finish| finish.place);
+        standings
+    }
+
+    pub fn remaining_prizes(&self) -> Vec<u32> { // Gains des places encore à attribuer (de la première à celle du prochain éliminé)
+        let mut prizes = self.payouts.prizes(self.prize_pool, self.entrants());
+        prizes.truncate(self.players.len());
+        prizes
+    }
+
+    fn starting_stacks(&self) -> Vec<u32> { // Tapis au début de la main en cours
+        self.players.iter().map(|p| p.chips + p.total_bet).collect()
+    }
+
+    pub fn icm_equities(&mut self) -> Vec<f64> { // Part de la dotation restante attendue par chaque joueur selon l'ICM
+        let (stacks, prizes) = (self.starting_stacks(), self.remaining_prizes());
+        icm_equities(&stacks, &prizes, ICM_ITERATIONS, &mut self.rng)
+    }
+
+    fn near_bubble(&self) -> bool { // À deux éliminations ou moins des places payées, ou déjà dans l'argent
+        self.prize_pool > 0 && self.players.len() <= self.payouts.prizes(self.prize_pool, self.entrants()).len() + 2
+    }
+
+    fn bubble_factor(&mut self, player_index: usize) -> f64 { // Pression ICM face au plus gros tapis adverse encore en jeu (1 loin de la bulle)
+        if !self.near_bubble() {
+            return 1.0;
+        }
+        let stacks = self.starting_stacks();
+        let villain = (0..self.players.len())
+            .filter(|&i| i != player_index && self.players[i].is_active)
+            .max_by_key(|&i| stacks[i]);
+        match villain {
+            Some(villain) => {
+                let prizes = self.remaining_prizes();
+                bubble_factor(&stacks, &prizes, player_index, villain, ICM_ITERATIONS, &mut self.rng)
+            }
+            None => 1.0,
+        }
+    }
+
+    fn show_standings(&mut self) { // Affiche le classement final et les gains
+        say!(self, "+==================== Classement final ====================+");
+        for finish in self.standings() {
+            if self.prize_pool > 0 {
+                say!(self, "| {:>2}. {:<20} {:>8}", finish.place, finish.name, finish.prize);
+            } else {
+                say!(self, "| {:>2}. {}", finish.place, finish.name);
+            }
+        }
+    }
+
+    fn eliminate_busted_players(&mut self) { // Enregistre la place des joueurs sans jetons puis les retire de la table
+        let busted: Vec<(String, u32)> = self.players.iter()
+            .filter(|p| p.chips == 0)
+            .map(|p| (p.name.clone(), p.total_bet)) // Un joueur éliminé a misé tout son tapis de départ
+            .collect();
+        let entrants = self.entrants();
+        self.players.retain(|p| p.chips > 0); // Les sièges restent numérotés: le bouton et les blinds n'en sont pas décalés
+        for finish in rank_eliminations(&busted, self.players.len(), &self.payouts, self.prize_pool, entrants) {
+            self.emit(GameEvent::PlayerEliminated { player: finish.name.clone(), place: finish.place, prize: finish.prize });
+            self.finishes.push(finish);
+        }
+    }
+
+    fn update_blind_level(&mut self) { // Passe au niveau suivant de la structure quand son temps est écoulé et l'annonce
+        let Some(schedule) = self.blind_schedule.as_mut() else {
+            return;
+        };
+        let Some(&level) = schedule.start_hand() else {
+            return;
+        };
+        let number = schedule.level_number();
+        self.emit(GameEvent::LevelChanged { level: number, blinds: level });
+        self.small_blind = level.small_blind;
+        self.big_blind = level.big_blind;
+        self.ante = if self.big_blind_ante && level.ante > 0 { level.big_blind } else { level.ante }; // L'ante du niveau est par joueur; l'ante de grosse blind paie pour toute la table
+        if let BettingStructure::FixedLimit { raise_cap, .. } = self.betting_structure { // En limite fixe, les mises suivent la grosse blind
+            self.betting_structure = BettingStructure::FixedLimit { small_bet: level.big_blind, big_bet: 2 * level.big_blind, raise_cap };
+        }
+    }
+
+    fn move_button(&mut self) { // Place le bouton et les blinds de la main: la grosse blind avance toujours d'un joueur (règle du bouton mort)
+        let num_players = self.players.len();
+        match self.big_blind_seat {
+            None => { // Première main: les blinds suivent le bouton
+                let button = self.index_at_or_after_seat(self.button_seat);
+                let small_blind = if num_players == 2 { button } else { (button + 1) % num_players };
+                self.button_seat = self.players[button].seat;
+                self.small_blind_seat = self.players[small_blind].seat;
+                self.big_blind_seat = Some(self.players[(small_blind + 1) % num_players].seat);
+            }
+            Some(previous_big_blind) => {
+                let big_blind = self.index_at_or_after_seat(previous_big_blind + 1);
+                if num_players == 2 { // En tête-à-tête, l'autre joueur a le bouton et paie la petite blind
+                    self.button_seat = self.players[(big_blind + 1) % 2].seat;
+                    self.small_blind_seat = self.button_seat;
+                } else { // Le bouton et la petite blind peuvent tomber sur un siège libéré par un joueur éliminé
+                    self.button_seat = self.small_blind_seat;
+                    self.small_blind_seat = previous_big_blind;
+                }
+                self.big_blind_seat = Some(self.players[big_blind].seat);
+                self.mark_missed_big_blinds(previous_big_blind, self.players[big_blind].seat);
+            }
+        }
+    }
+
+    fn mark_missed_big_blinds(&mut self, previous_big_blind: usize, big_blind: usize) { // Les joueurs absents dont le siège a été sauté par la grosse blind la devront à leur retour
+        for player in &mut self.sitting_out {
+            let skipped = if previous_big_blind < big_blind {
+                player.seat > previous_big_blind && player.seat < big_blind
+            } else {
+                player.seat > previous_big_blind || player.seat < big_blind
+            };
+            if skipped {
+                player.missed_big_blind = true;
+            }
+        }
+    }
+
+    fn index_at_or_after_seat(&self, seat: usize) -> usize { // Premier joueur assis à ce siège ou après, en revenant au début de la table
+        self.players.iter().position(|p| p.seat >= seat).unwrap_or(0)
     }
 
-    fn advance_dealer(&mut self) { // Passe le rôle de croupier au joueur suivant
-        self.dealer_position = (self.dealer_position + 1) % self.players.len();
+    fn button_index(&self) -> usize { // Joueur qui parle en dernier après le flop (celui du bouton, ou le dernier avant un bouton mort)
+        (self.index_at_or_after_seat(self.button_seat + 1) + self.players.len() - 1) % self.players.len()
     }
 
     pub fn new_round(&mut self) {  // Démarre un nouveau tour de jeu
-        println!("+==================== Nouveau tour ====================+");
+        let stacks = self.players.iter().map(|p| (p.name.clone(), p.chips)).collect();
+        self.emit(GameEvent::HandStarted { stacks });
         self.reset_round();
+        self.update_blind_level();
+        self.move_button();
         self.collect_blinds();
         self.deal_hole_cards();
-        self.betting_round("Pré-flop");
-        self.deal_community_cards(3);
-        self.betting_round("Flop");
-        self.deal_community_cards(1);
-        self.betting_round("Turn");
-        self.deal_community_cards(1);
-        self.betting_round("River");
-        let winner_index = self.determine_winner();
-        println!("| Le gagnant de ce tour est {}", self.players[winner_index].name);
-        self.players[winner_index].chips += self.pot;
-        self.players.retain(|p| p.chips > 0);
-        println!("| Nombre de joueurs restants: {}", self.players.len());
+        let mut hands_revealed = false;
+        for (stage, card_count) in [("Pré-flop", 0), ("Flop", 3), ("Turn", 1), ("River", 1)] {
+            if self.active_player_count() == 1 { // Tous les autres se sont couchés: la main s'arrête
+                break;
+            }
+            if card_count > 0 {
+                self.start_street(stage);
+                if !hands_revealed && !self.needs_betting() { // Plus de mise possible: les mains sont retournées avant la suite du tableau
+                    self.show_all_in_equities();
+                    hands_revealed = true;
+                }
+                self.deal_community_cards(stage, card_count);
+            }
+            if self.needs_betting() {
+                self.betting_round(stage);
+            }
+            self.return_uncalled_bet(); // Avant que la rue suivante ne remette les mises à zéro
+        }
+        if self.active_player_count() == 1 {
+            self.award_uncontested_pot();
+        } else {
+            self.distribute_pots();
+        }
+        match self.mode {
+            GameMode::Tournament => self.eliminate_busted_players(),
+            GameMode::Cash { .. } => self.bench_busted_players(),
+        }
+        say!(self, "| Nombre de joueurs restants: {}", self.players.len());
+    }
+
+    fn start_street(&mut self, stage: &str) { // Remet les mises de la rue à zéro avant le flop, le turn et la river
+        for player in &mut self.players {
+            player.current_bet = 0;
+        }
+        let bet_size = self.betting_structure.bet_size(stage, self.big_blind);
+        self.betting = BettingRound::new(self.players.len(), 0, bet_size, self.betting_structure);
+    }
+
+    fn active_player_count(&self) -> usize { // Nombre de joueurs encore en lice pour le pot
+        self.players.iter().filter(|p| p.is_active).count()
+    }
+
+    fn needs_betting(&self) -> bool { // Faux si plus personne ne peut miser (tout le monde est à tapis sauf au plus un joueur déjà à niveau)
+        let highest_bet = self.players.iter().filter(|p| p.is_active).map(|p| p.current_bet).max().unwrap_or(0);
+        let can_act: Vec<&Player> = self.players.iter().filter(|p| p.is_active && !p.is_all_in()).collect();
+        match can_act.len() {
+            0 => false,
+            1 => can_act[0].current_bet < highest_bet,
+            _ => true,
+        }
+    }
+
+    fn show_all_in_equities(&mut self) { // Affiche l'équité de chaque joueur à tapis, mains connues
+        let active: Vec<usize> = (0..self.players.len()).filter(|&i| self.players[i].is_active).collect();
+        for &i in &active {
+            let ranges: Vec<Range> = active.iter()
+                .filter(|&&j| j != i)
+                .map(|&j| Range::exact(self.players[j].hand[0], self.players[j].hand[1]))
+                .collect();
+            let equity = calculate_equity_vs_ranges(&self.players[i].hand, &self.community_cards, &ranges, EQUITY_ITERATIONS, &mut self.rng);
+            let player = &self.players[i];
+            let event = GameEvent::HandRevealed { player: player.name.clone(), cards: [player.hand[0], player.hand[1]], equity };
+            self.emit(event);
+        }
+    }
+
+    fn return_uncalled_bet(&mut self) { // La mise que personne n'a suivie revient à son auteur au lieu de former un pot à lui seul
+        if let Some((i, amount)) = return_uncalled_bet(&mut self.players) {
+            self.pot -= amount;
+            self.emit(GameEvent::UncalledBetReturned { player: self.players[i].name.clone(), amount });
+        }
+    }
+
+    fn award_uncontested_pot(&mut self) { // Donne le pot au dernier joueur restant, sans abattage
+        if let Some(winner) = self.players.iter_mut().find(|p| p.is_active) {
+            winner.chips += self.pot;
+            let event = GameEvent::PotAwarded { player: winner.name.clone(), amount: self.pot, pot: 0, winners: 1, uncontested: true };
+            self.emit(event);
+        }
+        self.pot = 0;
     }
 
     fn reset_round(&mut self) { // Réinitialise les paramètres pour un nouveau tour
         self.reset_deck();
         self.pot = 0;
         self.community_cards.clear();
-        self.current_bet = 0;
         self.last_bettor = None;
+        self.straddler = None;
         for player in &mut self.players {
             player.current_bet = 0;
+            player.total_bet = 0;
             player.is_active = true;
             player.hand.clear();
         }
     }
 
-    fn collect_blinds(&mut self) { // Collecte les blinds pour le tour
-        let sb_pos = (self.dealer_position + 1) % self.players.len();
-        let bb_pos = (self.dealer_position + 2) % self.players.len();
-        self.place_blind(sb_pos, self.small_blind, "small");
-        self.place_blind(bb_pos, self.big_blind, "big");
-        self.current_bet = self.players[bb_pos].current_bet.max(self.players[sb_pos].current_bet);
+    fn collect_blinds(&mut self) { // Collecte les antes, les blinds et le straddle éventuel pour le tour
+        if self.players.iter().all(|p| p.seat != self.button_seat) {
+            say!(self, "| Bouton mort au siège {}.", self.button_seat + 1);
+        }
+        if self.ante > 0 && !self.big_blind_ante {
+            for i in 0..self.players.len() {
+                self.place_ante(i, self.ante);
+            }
+        }
+        let sb_pos = self.small_blind_position();
+        let bb_pos = self.big_blind_position();
+        match sb_pos {
+            Some(sb_pos) => self.place_blind(sb_pos, self.small_blind, BlindKind::Small),
+            None => say!(self, "| Pas de petite blind: le siège {} est vide.", self.small_blind_seat + 1),
+        }
+        self.place_blind(bb_pos, self.big_blind, BlindKind::Big);
+        if self.ante > 0 && self.big_blind_ante { // La grosse blind passe avant l'ante si le joueur est trop court pour les deux
+            self.place_ante(bb_pos, self.ante);
+        }
+        for i in 0..self.players.len() { // Un joueur de retour paie la grosse blind manquée, sauf s'il est déjà aux blinds
+            if self.players[i].missed_big_blind && i != bb_pos && Some(i) != sb_pos {
+                self.place_blind(i, self.big_blind, BlindKind::MissedBig);
+            }
+            self.players[i].missed_big_blind = false;
+        }
+        let current_bet = self.big_blind; // Les autres joueurs doivent la grosse blind complète, même si elle est à tapis pour moins
+        let bet_size = self.betting_structure.bet_size("Pré-flop", self.big_blind);
+        self.betting = BettingRound::new(self.players.len(), current_bet, bet_size, self.betting_structure);
+        if self.straddle {
+            self.post_straddle((bb_pos + 1) % self.players.len());
+        }
+    }
+
+    fn place_ante(&mut self, position: usize, amount: u32) { // Les antes vont au pot sans compter dans la mise à égaler
+        let player = &mut self.players[position];
+        let ante = player.chips.min(amount);
+        player.chips -= ante;
+        player.total_bet += ante;
+        self.pot += ante;
+        let event = GameEvent::BlindPosted { player: player.name.clone(), kind: BlindKind::Ante, amount: ante, all_in: ante < amount };
+        self.emit(event);
     }
 
-    fn place_blind(&mut self, position: usize, amount: u32, blind_type: &str) { // Place une blind pour un joueur donné
+    fn post_straddle(&mut self, position: usize) { // Straddle de deux grosses blinds, seulement si le joueur le couvre
+        let amount = 2 * self.big_blind;
+        if self.players.len() < 3 || self.players[position].chips <= amount {
+            return;
+        }
+        let player = &mut self.players[position];
+        player.chips -= amount;
+        player.current_bet = amount;
+        player.total_bet += amount;
+        self.pot += amount;
+        let event = GameEvent::BlindPosted { player: player.name.clone(), kind: BlindKind::Straddle, amount, all_in: false };
+        self.emit(event);
+        self.betting.post_straddle(amount);
+        self.straddler = Some(position);
+    }
+
+    pub fn small_blind_position(&self) -> Option<usize> { // None si le joueur de ce siège a été éliminé (petite blind morte)
+        self.players.iter().position(|p| p.seat == self.small_blind_seat)
+    }
+
+    pub fn big_blind_position(&self) -> usize {
+        self.big_blind_seat.map_or(0, |seat| self.index_at_or_after_seat(seat))
+    }
+
+    pub fn first_to_act(&self, stage: &str) -> usize { // Avant le flop, le joueur après la grosse blind ou le straddle (le bouton en tête-à-tête); ensuite, le premier joueur après le bouton
+        match stage {
+            "Pré-flop" => (self.straddler.unwrap_or_else(|| self.big_blind_position()) + 1) % self.players.len(),
+            _ => (self.button_index() + 1) % self.players.len(),
+        }
+    }
+
+    fn place_blind(&mut self, position: usize, amount: u32, kind: BlindKind) { // Place une blind pour un joueur donné
         let player = &mut self.players[position];
         let blind_amount = player.chips.min(amount);
         player.chips -= blind_amount;
         player.current_bet = blind_amount;
+        player.total_bet += blind_amount;
         self.pot += blind_amount;
-        if blind_amount < amount {
-            println!("| {} posted {} {} blind (all-in)", player.name, blind_amount, blind_type);
-        } else {
-            println!("| {} posted {} {}", player.name, blind_amount, blind_type);
-        }
+        let event = GameEvent::BlindPosted { player: player.name.clone(), kind, amount: blind_amount, all_in: blind_amount < amount };
+        self.emit(event);
     }
 
     fn deal_hole_cards(&mut self) { // Distribue les cartes cachées aux joueurs
         for _ in 0..2 {
             for player in &mut self.players {
-                player.hand.push(self.deck.pop().unwrap());
+                player.hand.push(self.deck.deal().unwrap());
             }
         }
-        for player in &self.players {
-            println!("{} a reçu: {} et {}", player.name, player.hand[0].to_string(), player.hand[1].to_string());
-        }
+        let hands = self.players.iter().map(|p| (p.name.clone(), [p.hand[0], p.hand[1]])).collect();
+        self.emit(GameEvent::HoleCardsDealt { hands }); // Les cartes restent cachées jusqu'à l'abattage
     }
 
-    fn deal_community_cards(&mut self, count: usize) { // Distribue des cartes communes pour le tour
-        for _ in 0..count {
-            if let Some(card) = self.deck.pop() {
-                self.community_cards.push(card);
-            }
-        }
-        println!("Cartes communes: [{}]", self.community_cards.iter().map(|c| c.to_string()).collect::<Vec<_>>().join(", "));
+    fn deal_community_cards(&mut self, stage: &str, count: usize) { // Distribue des cartes communes pour le tour
+        self.deck.burn();
+        let cards: Vec<Card> = (0..count).filter_map(|_| self.deck.deal()).collect();
+        self.community_cards.extend(&cards);
+        let board = self.community_cards.clone();
+        self.emit(GameEvent::StreetDealt { street: stage.to_string(), cards, board });
     }
 
     fn betting_round(&mut self, stage: &str) { // Gère un tour de mise pour un stade donné
-        println!("+==================== {} ====================+", stage);
-        self.show_human_advice(stage);
+        say!(self, "+==================== {} ====================+", stage);
         let num_players = self.players.len();
-        let starting_index = match stage {
-            "Pré-flop" => (self.dealer_position + 3) % num_players,
-            _ => (self.dealer_position + 1) % num_players
-        };
-        let mut ordered_indices: Vec<usize> = (0..num_players)
+        let starting_index = self.first_to_act(stage);
+        let mut to_act: VecDeque<usize> = (0..num_players)
             .map(|i| (starting_index + i) % num_players)
-            .filter(|&i| self.players[i].is_active && self.players[i].chips > 0)
+            .filter(|&i| self.players[i].is_active && !self.players[i].is_all_in())
             .collect();
-        let mut last_raiser = None;
-        let mut current_bet = self.current_bet;
-        loop {
-            let mut action_occurred = false;
-            let mut new_ordered_indices = Vec::new();
-            for &i in &ordered_indices {
-                let required = current_bet.saturating_sub(self.players[i].current_bet);
-                let bet = self.get_bet(i, required);
-                let player = &mut self.players[i];
-                
-                let actual_bet = bet.min(player.chips);
-                
-                match actual_bet { // Gestion des actions des joueurs
-                    0 if required > 0 => {
-                        player.is_active = false;
-                        println!("| {} se couche.", player.name);
-                    },
-                    0 => (),
-                    _ => {
-                        let total_bet = player.current_bet.saturating_add(actual_bet);
-                        player.current_bet = total_bet;
-                        player.chips = player.chips.saturating_sub(actual_bet);
-                        self.pot = self.pot.saturating_add(actual_bet);
-                        
-                        if total_bet > current_bet {
-                            current_bet = total_bet;
-                            last_raiser = Some(i);
-                            action_occurred = true;
-                            println!("| {} relance à {}.", player.name, total_bet);
-                        } else {
-                            println!("| {} suit avec {}.", player.name, actual_bet);
-                        }
-                    }
-                }
-                if player.is_active && player.chips > 0 && player.current_bet < current_bet {
-                    new_ordered_indices.push(i);
-                }
-            }
-            ordered_indices = new_ordered_indices;
-            if !action_occurred {
+        while let Some(i) = to_act.pop_front() {
+            if self.active_player_count() == 1 { // Plus d'adversaire: inutile de demander une action
                 break;
             }
-        }
-        self.current_bet = current_bet;
-        self.last_bettor = last_raiser;
-        println!("| Pot total: {} jetons.", self.pot);
-    }
+            let action = self.get_action(i, stage);
+            let bet = self.legal_actions(i).validate(action).unwrap_or(0); // Déjà validée par get_action
+            let player = &mut self.players[i];
 
-    fn get_bet(&self, player_index: usize, required: u32) -> u32 { // Obtient la mise d'un joueur pour un tour donné
-        let player = &self.players[player_index];
-        
-        if required > player.chips {
-            return 0;
-        }
-    
-        if player.is_human { // Si le joueur est humain, attend une entrée de l'utilisateur
-            println!("| {}, vous avez {} jetons. Mise requise: {}. Entrez votre mise (0 pour passer): ", player.name, player.chips, required);
-            loop {
-                let mut input = String::new();
-                std::io::stdin().read_line(&mut input).unwrap();
-                let bet = input.trim().parse::<u32>();
-                match bet {
-                    Ok(bet) if bet > player.chips => {
-                        println!("| Vous n'avez pas assez de jetons. Maximum possible: {}", player.chips);
-                    }
-                    Ok(bet) if bet >= required => return bet,
-                    Ok(0) if required == 0 => return 0,
-                    Ok(0) => return 0,
-                    Ok(_) => println!("| Mise insuffisante. Minimum requis: {}", required),
-                    _ => println!("| Entrée invalide."),
-                }
+            if action == Action::Fold {
+                player.is_active = false;
+                let event = GameEvent::PlayerActed { player: player.name.clone(), action, amount: 0, all_in: false };
+                self.emit(event);
+                continue;
             }
-        } else { // Si le joueur est une IA, détermine la mise en fonction de la difficulté
-            let mut rng = rand::thread_rng();
-            let raise_chance = match player.name.as_str() {
-                "IA-Facile" => 0.2,
-                "IA-Intermédiaire" => 0.4,
-                "IA-Difficile" => 0.6,
-                "IA-Extrêmement-Difficile" => 0.8,
-                _ => 0.0,
-            };
-    
-            let max_possible_bet = player.chips;
-            
-            if rng.gen_bool(raise_chance) && player.chips > required { 
-                let max_raise = max_possible_bet.saturating_sub(required);
-                if max_raise > 0 {
-                    required + rng.gen_range(1..=max_raise)
-                } else {
-                    required
-                }
-            } else if player.chips >= required {
-                required
+            player.current_bet += bet;
+            player.total_bet += bet;
+            player.chips -= bet;
+            self.pot += bet;
+            let total_bet = player.current_bet;
+            let (name, all_in) = (player.name.clone(), player.chips == 0);
+            let previous_bet = self.betting.current_bet;
+            let action = if self.betting.record_action(i, total_bet) {
+                self.last_bettor = Some(i);
+                to_act = (1..num_players) // Tous les autres joueurs doivent répondre à la relance
+                    .map(|offset| (i + offset) % num_players)
+                    .filter(|&j| self.players[j].is_active && !self.players[j].is_all_in())
+                    .collect();
+                if previous_bet == 0 { Action::Bet(total_bet) } else { Action::Raise(total_bet) }
+            } else if bet == 0 {
+                Action::Check
             } else {
-                0
-            }
+                Action::Call
+            };
+            self.emit(GameEvent::PlayerActed { player: name, action, amount: bet, all_in });
         }
+        say!(self, "| Pot total: {} jetons.", self.pot);
+    }
+
+    pub fn legal_actions(&self, player_index: usize) -> LegalActions { // Actions permises au joueur dont c'est le tour
+        self.betting.legal_actions(player_index, &self.players[player_index], self.pot)
+    }
+
+    fn get_action(&mut self, player_index: usize, stage: &str) -> Action { // Obtient l'action d'un joueur auprès de son agent, toujours conforme aux règles
+        let equity = self.calculate_win_probability(player_index);
+        let bubble_factor = self.bubble_factor(player_index);
+        let legal = self.legal_actions(player_index);
+        let advice = if self.players[player_index].is_human { self.human_advice(player_index, stage, &equity, bubble_factor) } else { Vec::new() };
+        let position = self.get_position(player_index);
+        let opponents = self.active_player_count() - 1;
+        let shared_screen = self.has_multiple_humans();
+
+        let player = &self.players[player_index];
+        let view = PlayerView {
+            name: &player.name,
+            stage,
+            context: DecisionContext {
+                hand: &player.hand,
+                community: &self.community_cards,
+                chips: player.chips,
+                street_bet: player.current_bet,
+                to_call: legal.to_call.min(legal.stack),
+                pot: self.pot,
+                big_blind: self.big_blind,
+                equity: equity.equity,
+                position,
+                opponents,
+                bubble_factor,
+            },
+            legal: &legal,
+            advice: &advice,
+            shared_screen,
+        };
+        let agent = self.agents.entry(player.name.clone()).or_insert_with(|| default_agent(player));
+        let action = agent.decide(&view, &mut self.rng).unwrap_or_else(|| { // Fin de l'entrée: le joueur passe s'il le peut, sinon se couche
+            self.input_closed = true;
+            Action::Check
+        });
+        legal.nearest(action)
+    }
+
+    fn has_multiple_humans(&self) -> bool { // Vrai si plusieurs humains partagent le terminal
+        self.players.iter().filter(|p| p.is_human).count() > 1
     }
 
-    fn show_human_advice(&self, stage: &str) { // Affiche des conseils pour les joueurs humains
-        for (index, player) in self.players.iter().enumerate() {
-            if player.is_human && player.is_active {
-                let probability = self.calculate_win_probability(player);
-                println!("{} a une probabilité de gagner de {:.2}% pour la phase {}.", player.name, probability * 100.0, stage);
-                self.provide_advice(player, probability, index, stage);
+    fn human_advice(&mut self, player_index: usize, stage: &str, equity: &Equity, bubble_factor: f64) -> Vec<String> { // Équité, conseils et pression ICM montrés à un joueur humain
+        let icm = if self.prize_pool > 0 { Some(self.icm_equities()[player_index]) } else { None };
+        let player = &self.players[player_index];
+        let mut advice = vec![
+            format!("{} a une équité de {:.2}% pour la phase {} ({}).", player.name, equity.equity * 100.0, stage, equity),
+            self.provide_advice(player, equity.equity, player_index, stage),
+        ];
+        if let Some(icm) = icm {
+            let remaining: u32 = self.remaining_prizes().iter().sum();
+            advice.push(format!("| Équité ICM de {}: {:.0} sur {} restants à distribuer.", player.name, icm, remaining));
+            if bubble_factor > 1.05 {
+                advice.push(format!("| Près de la bulle: perdre un tapis coûte {:.1} fois plus que n'en rapporte le gagner, resserrez vos suivis.", bubble_factor));
             }
         }
+        advice
     }
 
-    fn calculate_win_probability(&self, player: &Player) -> f64 { // Calcule la probabilité de victoire d'un joueur
-        let hand_value = self.evaluate_hand(&player.hand, &self.community_cards);
-        (hand_value as f64 / 1000.0).min(1.0)
+    fn calculate_win_probability(&mut self, player_index: usize) -> Equity { // Estime l'équité d'un joueur contre les adversaires encore en jeu
+        let opponents = self.active_player_count().saturating_sub(1);
+        calculate_equity(&self.players[player_index].hand, &self.community_cards, opponents, EQUITY_ITERATIONS, &mut self.rng)
     }
 
-    fn provide_advice(&self, player: &Player, probability: f64, player_index: usize, stage: &str) { // Fournit des conseils pour un joueur donné
+    fn provide_advice(&self, player: &Player, probability: f64, player_index: usize, stage: &str) -> String { // Conseil pour un joueur donné
         let pot_odds = self.calculate_pot_odds(player);
         let position = self.get_position(player_index);
-        let advice = if probability > 0.7 {
+        let fair_share = 1.0 / self.active_player_count().max(1) as f64; // Équité d'une main moyenne
+        let advice = if probability > 2.0 * fair_share {
             "Excellente main, envisagez de relancer!"
-        } else if probability > 0.4 && pot_odds > 2.5 {
-            "Main décente avec de bons pot odds, suivez le pari."
-        } else if probability > 0.4 && position == "late" {
+        } else if pot_odds > 0.0 && probability > 1.0 / (pot_odds + 1.0) {
+            "Votre équité couvre les pot odds, suivez le pari."
+        } else if probability > fair_share && position == Position::Late {
             "Main décente en position tardive, envisagez de suivre ou relancer."
-        } else if pot_odds > 3.0 {
-            "Les pot odds sont favorables, envisagez de suivre."
+        } else if pot_odds == 0.0 {
+            "Main moyenne, passez sans miser."
         } else {
-            "Main faible, envisagez de passer."
+            "Main faible, envisagez de vous coucher."
         };
-        println!("| Conseils pour {} lors de la {}: {}", player.name, stage, advice);
+        format!("| Conseils pour {} lors de la {}: {}", player.name, stage, advice)
     }
 
     fn calculate_pot_odds(&self, player: &Player) -> f64 { // Calcule les pot odds pour un joueur donné
-        if self.current_bet == 0 || player.current_bet >= self.current_bet {
+        let call_amount = self.betting.to_call(player);
+        if call_amount == 0 {
             return 0.0;
         }
-        let call_amount = self.current_bet - player.current_bet;
         (self.pot as f64) / (call_amount as f64)
     }
 
-    fn get_position(&self, player_index: usize) -> &str { // Détermine la position d'un joueur
-        let num_players = self.players.len();
-        if player_index < num_players / 3 {
-            "early"
-        } else if player_index < 2 * num_players / 3 {
-            "middle"
-        } else {
-            "late"
-        }
+    fn get_position(&self, player_index: usize) -> Position { // Détermine la position d'un joueur par rapport au bouton
+        Position::from_seat(player_index, self.button_index(), self.players.len())
     }
 
-    fn determine_winner(&self) -> usize { // Détermine le gagnant du tour
-        let mut best_score = 0;
-        let mut winner_index = 0;
-        for (i, player) in self.players.iter().enumerate() {
-            if !player.is_active { continue; }
-            let score = self.evaluate_hand(&player.hand, &self.community_cards);
-            if score > best_score {
-                best_score = score;
-                winner_index = i;
+    fn distribute_pots(&mut self) { // Partage chaque pot entre les meilleures mains parmi les joueurs éligibles
+        for i in 0..self.players.len() { // Chaque joueur encore en lice montre sa main une seule fois, quel que soit le nombre de pots
+            if self.players[i].is_active {
+                let player = &self.players[i];
+                let rank = evaluate_cards(&player.hand, &self.community_cards).rank();
+                let event = GameEvent::Showdown { player: player.name.clone(), cards: [player.hand[0], player.hand[1]], rank };
+                self.emit(event);
             }
         }
-        winner_index
+        let pots = build_pots(&self.players);
+        for (pot_number, pot) in pots.iter().enumerate() {
+            let winners = self.determine_winners(pot);
+            let share = pot.amount / winners.len() as u32;
+            let odd_chips = pot.amount % winners.len() as u32;
+            for (n, &winner_index) in winners.iter().enumerate() {
+                let amount = share + u32::from((n as u32) < odd_chips); // Les jetons indivisibles vont aux premiers sièges après le bouton
+                let player = &mut self.players[winner_index];
+                player.chips += amount;
+                let event = GameEvent::PotAwarded { player: player.name.clone(), amount, pot: pot_number, winners: winners.len(), uncontested: false };
+                self.emit(event);
+            }
+        }
+        self.pot = 0;
     }
 
-    fn evaluate_hand(&self, hand: &[Card], community: &[Card]) -> u32 { // Évalue la force d'une main de poker
-        let mut all_cards = hand.to_vec();
-        all_cards.extend_from_slice(community);
-        all_cards.sort_by(|a, b| b.rank_value().cmp(&a.rank_value()));
-        if let Some(sf_high) = self.is_straight_flush(&all_cards) {
-            return if sf_high == 14 { 1000 } else { 900 + sf_high };
-        }
-        if let Some(quad_value) = self.get_rank_value_of_multiple(&all_cards, 4) {
-            return 800 + quad_value;
-        }
-        if let Some(fh_value) = self.get_full_house_value(&all_cards) {
-            return 700 + fh_value;
-        }
-        if let Some(flush_value) = self.evaluate_flush(&all_cards) {
-            return 600 + flush_value;
-        }
-        if let Some(straight_high) = self.get_straight_highest(&all_cards) {
-            return 500 + straight_high;
-        }
-        if let Some(triple_value) = self.get_rank_value_of_multiple(&all_cards, 3) {
-            return 400 + triple_value;
-        }
-        if let Some(tp_value) = self.get_two_pair_value(&all_cards) {
-            return 300 + tp_value;
+    fn determine_winners(&self, pot: &Pot) -> Vec<usize> { // Détermine les gagnants d'un pot, dans l'ordre des sièges à partir du bouton
+        let ranks: Vec<(usize, HandValue)> = pot.eligible_players.iter()
+            .map(|&i| (i, evaluate_cards(&self.players[i].hand, &self.community_cards)))
+            .collect();
+        let best = match ranks.iter().map(|(_, rank)| *rank).max() {
+            Some(best) => best,
+            None => return Vec::new(),
+        };
+        let num_players = self.players.len();
+        let button = self.button_index();
+        let mut winners: Vec<usize> = ranks.iter()
+            .filter(|(_, rank)| *rank == best)
+            .map(|(i, _)| *i)
+            .collect();
+        winners.sort_by_key(|&i| (i + num_players - button - 1) % num_players);
+        winners
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent::BotAgent;
+    use crate::blinds::{BlindLevel, LevelDuration};
+    use crate::card::parse_cards;
+    use crate::io::SilentOutput;
+    use std::sync::mpsc::channel;
+
+    struct Always(Action); // Agent de test qui répète toujours la même action
+
+    impl PlayerAgent for Always {
+        fn decide(&mut self, _view: &PlayerView, _rng: &mut dyn RngCore) -> Option<Action> {
+            Some(self.0)
         }
-        if let Some(pair_value) = self.get_rank_value_of_multiple(&all_cards, 2) {
-            return 200 + pair_value;
+    }
+
+    fn silent_game(seed: u64) -> PokerGame {
+        table(&["Alice", "Bob", "Carole"], seed)
+    }
+
+    fn table(names: &[&str], seed: u64) -> PokerGame { // Un joueur par siège, dans l'ordre des noms
+        let players = names.iter().map(|name| Player::new_ai(name.to_string(), Difficulty::default())).collect();
+        let mut game = PokerGame::with_seed(players, seed);
+        game.output = Box::new(SilentOutput);
+        game
+    }
+
+    fn bust(game: &mut PokerGame, seat: usize) { // Élimine le joueur assis à ce siège entre deux mains
+        game.players.retain(|p| p.seat != seat);
+    }
+
+    fn seat_of(game: &PokerGame, index: usize) -> usize {
+        game.players[index].seat
+    }
+
+    fn set_agents(game: &mut PokerGame, agent: impl Fn() -> Box<dyn PlayerAgent>) {
+        for name in ["Alice", "Bob", "Carole"] {
+            game.set_agent(name, agent());
         }
-        all_cards.iter().take(5).map(|c| c.rank_value()).sum()
     }
 
-    fn is_straight_flush(&self, cards: &[Card]) -> Option<u32> {   // Vérifie si une main est une quinte flush
-        let flush_suit = self.get_flush_suit(cards)?;
-        let suited_cards: Vec<&Card> = cards.iter().filter(|c| c.suit == *flush_suit).collect();
-        if suited_cards.len() < 5 { return None; }
-        let mut ranks: Vec<u32> = suited_cards.iter().map(|c| c.rank_value()).collect();
-        ranks.sort();
-        ranks.dedup();
-        let mut check_ranks = ranks.clone();
-        if check_ranks.contains(&14) { check_ranks.push(1); check_ranks.sort(); }
-        check_ranks.windows(5).find(|w| w[4] - w[0] == 4)
-            .map(|w| if w[4] == 14 && w[0] == 1 { 5 } else { w[4] })
+    #[test]
+    fn same_seed_deals_the_same_next_hand_whatever_the_actions() {
+        let mut played = silent_game(42);
+        let mut folded = silent_game(42);
+        set_agents(&mut played, || Box::new(BotAgent(Difficulty::default()))); // Décisions aléatoires et simulations jusqu'à l'abattage
+        set_agents(&mut folded, || Box::new(Always(Action::Fold))); // Main terminée avant le flop
+        played.new_round();
+        folded.new_round();
+
+        for game in [&mut played, &mut folded] {
+            set_agents(game, || Box::new(Always(Action::Call)));
+            game.new_round();
+        }
+        let hands = |game: &PokerGame| game.players.iter().map(|p| (p.name.clone(), p.hand.clone())).collect::<Vec<_>>();
+        assert_eq!(hands(&played), hands(&folded));
+        assert_eq!(played.community_cards, folded.community_cards);
+        assert_eq!(played.community_cards.len(), 5);
     }
 
-    fn get_flush_suit<'a>(&self, cards: &'a [Card]) -> Option<&'a crate::card::Suit> { // Détermine la couleur d'une quinte flush
-        let mut suit_counts = HashMap::new();
-        for card in cards {
-            *suit_counts.entry(&card.suit).or_insert(0) += 1;
+    fn all_in_hand(chips: [u32; 3]) -> (PokerGame, Vec<GameEvent>) { // Une main où chacun fait tapis avant le flop
+        let mut game = silent_game(7);
+        for (player, chips) in game.players.iter_mut().zip(chips) {
+            player.chips = chips;
         }
-        suit_counts.iter()
-            .find(|(_, &count)| count >= 5)
-            .map(|(suit, _)| *suit)
+        set_agents(&mut game, || Box::new(Always(Action::AllIn)));
+        let (sender, events) = channel();
+        game.subscribe(Box::new(sender));
+        game.new_round();
+        let events = events.try_iter().collect();
+        (game, events)
     }
 
-    fn evaluate_flush(&self, cards: &[Card]) -> Option<u32> { // Évalue une couleur
-        let flush_suit = self.get_flush_suit(cards)?;
-        let mut flush_ranks: Vec<u32> = cards.iter()
-            .filter(|c| &c.suit == flush_suit)
-            .map(|c| c.rank_value())
-            .collect();
-        flush_ranks.sort_by(|a, b| b.cmp(a));
-        Some(flush_ranks.iter().take(5).sum())
+    #[test]
+    fn uncalled_all_in_is_returned_before_the_board_runs_out() {
+        let (game, events) = all_in_hand([1000, 200, 500]);
+        let returned: Vec<&GameEvent> = events.iter().filter(|e| matches!(e, GameEvent::UncalledBetReturned { .. })).collect();
+        assert_eq!(returned, [&GameEvent::UncalledBetReturned { player: "Alice".to_string(), amount: 500 }]);
+        let pots: u32 = events.iter()
+            .map(|e| match e {
+                GameEvent::PotAwarded { pot, amount, .. } => {
+                    assert!(*pot <= 1, "pas de pot secondaire à un seul joueur");
+                    *amount
+                }
+                _ => 0,
+            })
+            .sum();
+        assert_eq!(pots, 1200); // 3 x 200, puis 2 x 300 entre Alice et Carole
+        assert_eq!(game.players.iter().map(|p| p.chips).sum::<u32>(), 1700);
+        assert_eq!(game.community_cards.len(), 5);
     }
 
-    fn get_straight_highest(&self, cards: &[Card]) -> Option<u32> { // Détermine la valeur de la plus haute carte d'une quinte
-        let mut ranks: Vec<u32> = cards.iter()
-            .map(|c| c.rank_value())
+    #[test]
+    fn each_player_shows_down_once_whatever_the_number_of_pots() {
+        let (_, events) = all_in_hand([1000, 200, 500]);
+        let shown: Vec<&str> = events.iter()
+            .filter_map(|e| match e {
+                GameEvent::Showdown { player, .. } => Some(player.as_str()),
+                _ => None,
+            })
             .collect();
-        ranks.sort();
-        ranks.dedup();
-        let mut check_ranks = ranks.clone();
-        if check_ranks.contains(&14) { check_ranks.push(1); check_ranks.sort(); }
-        check_ranks.windows(5).rev()
-            .find(|w| w[4] - w[0] == 4)
-            .map(|w| w[4])
+        assert_eq!(shown, ["Alice", "Bob", "Carole"]);
+        let first_award = events.iter().position(|e| matches!(e, GameEvent::PotAwarded { .. })).unwrap();
+        let last_showdown = events.iter().rposition(|e| matches!(e, GameEvent::Showdown { .. })).unwrap();
+        assert!(last_showdown < first_award);
+    }
+
+    #[test]
+    fn short_all_in_big_blind_still_sets_the_full_call() {
+        let mut game = silent_game(1);
+        game.reset_round();
+        game.move_button();
+        let bb_pos = game.big_blind_position();
+        game.players[bb_pos].chips = 3; // Moins que la petite blind
+        game.collect_blinds();
+        assert_eq!(game.players[bb_pos].current_bet, 3);
+        let first = game.first_to_act("Pré-flop");
+        assert_eq!(game.legal_actions(first).to_call, game.big_blind);
+        let sb_pos = game.small_blind_position().unwrap();
+        assert_eq!(game.legal_actions(sb_pos).to_call, game.big_blind - game.small_blind);
     }
 
-    fn get_full_house_value(&self, cards: &[Card]) -> Option<u32> { // Détermine la valeur d'un full
-        let mut rank_counts = HashMap::new();
-        for card in cards {
-            *rank_counts.entry(card.rank_value()).or_insert(0) += 1;
+    #[test]
+    fn odd_chip_goes_to_the_first_winner_after_the_button() {
+        for (button_seat, expected) in [(0, [10, 0, 11]), (2, [11, 0, 10])] {
+            let mut game = silent_game(1);
+            game.button_seat = button_seat;
+            game.community_cards = parse_cards("Ts Js Qs Ks As").unwrap(); // Le tableau joue: Alice et Carole partagent
+            for (player, (hand, total_bet)) in game.players.iter_mut().zip([("2c 3d", 10), ("4c 5d", 1), ("6c 7d", 10)]) {
+                player.hand = parse_cards(hand).unwrap();
+                player.total_bet = total_bet;
+                player.chips = 0;
+            }
+            game.players[1].is_active = false; // Bob s'est couché: son jeton rend le pot indivisible
+            game.distribute_pots();
+            assert_eq!(game.players.iter().map(|p| p.chips).collect::<Vec<_>>(), expected);
         }
-        let mut triples = rank_counts.iter()
-            .filter(|(_, &v)| v >= 3)
-            .map(|(&k, _)| k)
-            .collect::<Vec<_>>();
-        triples.sort_by(|a, b| b.cmp(a));
-        let triple_value = triples.first()?;
-        let mut pairs = rank_counts.iter()
-            .filter(|(k, &v)| v >= 2 && *k != triple_value)
-            .map(|(&k, _)| k)
-            .collect::<Vec<_>>();
-        pairs.sort_by(|a, b| b.cmp(a));
-        pairs.first().map(|pair_value| triple_value * 10 + pair_value)
     }
 
+    #[test]
+    fn bust_on_the_next_small_blind_leaves_a_dead_small_blind() {
+        let mut game = table(&["Alice", "Bob", "Carole", "David"], 1);
+        game.move_button();
+        assert_eq!((game.button_seat, game.small_blind_seat, game.big_blind_seat), (0, 1, Some(2)));
+        bust(&mut game, 2); // La grosse blind de la main, qui aurait payé la petite blind suivante
+        game.move_button();
+        assert_eq!((game.button_seat, game.small_blind_seat, game.big_blind_seat), (1, 2, Some(3)));
+        assert_eq!(game.small_blind_position(), None);
+        assert_eq!(seat_of(&game, game.big_blind_position()), 3);
+    }
 
-fn get_two_pair_value(&self, cards: &[Card]) -> Option<u32> { // Détermine la valeur de deux paires
-    let mut rank_counts = HashMap::new();
-    for card in cards {
-        *rank_counts.entry(card.rank_value()).or_insert(0) += 1;
+    #[test]
+    fn bust_on_the_next_button_leaves_a_dead_button() {
+        let mut game = table(&["Alice", "Bob", "Carole", "David"], 1);
+        game.move_button();
+        bust(&mut game, 1); // La petite blind de la main, qui aurait eu le bouton suivant
+        game.move_button();
+        assert_eq!((game.button_seat, game.small_blind_seat, game.big_blind_seat), (1, 2, Some(3)));
+        assert!(game.players.iter().all(|p| p.seat != game.button_seat));
+        assert_eq!(seat_of(&game, game.button_index()), 0); // Alice parle en dernier après le flop
+        assert_eq!(seat_of(&game, game.small_blind_position().unwrap()), 2);
     }
 
-    let mut pairs: Vec<u32> = rank_counts.iter()
-        .filter(|(_, &count)| count >= 2)
-        .map(|(&rank, _)| rank)
-        .collect();
+    #[test]
+    fn heads_up_gives_the_button_to_the_small_blind() {
+        let mut game = table(&["Alice", "Bob", "Carole"], 1);
+        game.move_button();
+        assert_eq!((game.button_seat, game.small_blind_seat, game.big_blind_seat), (0, 1, Some(2)));
+        bust(&mut game, 0);
+        game.move_button();
+        assert_eq!(game.big_blind_seat, Some(1)); // La grosse blind avance toujours d'un joueur
+        assert_eq!((game.button_seat, game.small_blind_seat), (2, 2));
+        assert_eq!(seat_of(&game, game.first_to_act("Pré-flop")), 2); // Le bouton parle en premier avant le flop
+        assert_eq!(seat_of(&game, game.first_to_act("Flop")), 1);
+    }
 
-    pairs.sort_unstable_by(|a, b| b.cmp(a));
+    #[test]
+    fn player_back_from_sitting_out_owes_the_missed_big_blind() {
+        let mut game = table(&["Alice", "Bob", "Carole", "David"], 1);
+        game.mode = GameMode::Cash { max_buy_in: 2000 };
+        game.move_button();
+        game.sit_out("David").unwrap();
+        game.move_button();
+        assert_eq!(game.big_blind_seat, Some(0)); // La grosse blind saute le siège de David
+        assert!(game.sitting_out[0].missed_big_blind);
 
-    if pairs.len() >= 2 { // Si deux paires sont trouvées, retourne la valeur de la plus haute paire
-        Some(pairs[0] * 15 + pairs[1]) 
-    } else {
-        None
+        game.sit_in("David").unwrap();
+        game.reset_round();
+        game.move_button();
+        assert_eq!((game.small_blind_seat, game.big_blind_seat), (0, Some(1)));
+        game.collect_blinds();
+        let david = game.players.iter().find(|p| p.name == "David").unwrap();
+        assert_eq!(david.current_bet, game.big_blind);
+        assert!(!david.missed_big_blind);
     }
-}
 
-fn get_rank_value_of_multiple(&self, cards: &[Card], count: u32) -> Option<u32> { // Détermine la valeur d'une main avec plusieurs cartes de même rang
-    let mut rank_counts = HashMap::new();
-    for card in cards {
-        *rank_counts.entry(card.rank_value()).or_insert(0) += 1;
+    #[test]
+    fn big_blind_ante_covers_the_whole_table_at_each_level() {
+        let levels = vec![
+            BlindLevel { small_blind: 10, big_blind: 20, ante: 0, duration: LevelDuration::Hands(1) },
+            BlindLevel { small_blind: 50, big_blind: 100, ante: 10, duration: LevelDuration::Hands(1) },
+        ];
+        for (big_blind_ante, expected) in [(true, 100), (false, 10)] { // Ante de grosse blind: une grosse blind pour toute la table, et non l'ante d'un seul joueur
+            let mut game = silent_game(1);
+            game.big_blind_ante = big_blind_ante;
+            game.blind_schedule = Some(BlindSchedule::new(levels.clone()).unwrap());
+            game.update_blind_level();
+            assert_eq!(game.ante, 0);
+            game.update_blind_level();
+            assert_eq!(game.ante, expected);
+        }
     }
-
-    rank_counts.iter() 
-        .filter(|(_, &c)| c >= count)
-        .map(|(&rank, _)| rank)
-        .max()
 }
-
-}
\ No newline at end of file
@@ -0,0 +1,87 @@
+use crate::player::Player;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pot { // Un pot (principal ou secondaire) et les joueurs qui peuvent le remporter
+    pub amount: u32,
+    pub eligible_players: Vec<usize>,
+}
+
+pub fn build_pots(players: &[Player]) -> Vec<Pot> { // Découpe les mises de la main en pot principal et pots secondaires
+    let mut levels: Vec<u32> = players.iter()
+        .filter(|p| p.is_active && p.total_bet > 0)
+        .map(|p| p.total_bet)
+        .collect();
+    levels.sort_unstable();
+    levels.dedup();
+
+    let mut pots: Vec<Pot> = Vec::new();
+    let mut previous_level = 0;
+    for level in levels { // Chaque niveau d'all-in ferme un pot
+        let amount = players.iter()
+            .map(|p| p.total_bet.min(level).saturating_sub(previous_level))
+            .sum();
+        let eligible_players = players.iter().enumerate()
+            .filter(|(_, p)| p.is_active && p.total_bet >= level)
+            .map(|(i, _)| i)
+            .collect();
+        pots.push(Pot { amount, eligible_players });
+        previous_level = level;
+    }
+
+    let dead_money: u32 = players.iter() // Mises des joueurs couchés au-delà du dernier niveau
+        .map(|p| p.total_bet.saturating_sub(previous_level))
+        .sum();
+    if let Some(last) = pots.last_mut() {
+        last.amount += dead_money;
+    }
+    pots
+}
+
+pub fn return_uncalled_bet(players: &mut [Player]) -> Option<(usize, u32)> { // Rend au dernier miseur la part de sa mise de la rue que personne n'a suivie; renvoie le joueur et le montant
+    let top = (0..players.len()).max_by_key(|&i| players[i].current_bet)?;
+    let called = players.iter().enumerate()
+        .filter(|&(i, _)| i != top)
+        .map(|(_, p)| p.current_bet) // Les joueurs couchés dans la rue gardent leur mise, qui compte comme suivie
+        .max()
+        .unwrap_or(0);
+    let uncalled = players[top].current_bet.saturating_sub(called);
+    if uncalled == 0 {
+        return None;
+    }
+    let player = &mut players[top];
+    player.chips += uncalled;
+    player.current_bet -= uncalled;
+    player.total_bet -= uncalled;
+    Some((top, uncalled))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn player(total_bet: u32, is_active: bool) -> Player { // Joueur avec sa mise totale de la main
+        Player { total_bet, is_active, chips: 0, ..Player::new("Joueur".to_string(), false) }
+    }
+
+    #[test]
+    fn all_in_levels_layer_main_and_side_pots() {
+        let players = [player(50, true), player(200, true), player(200, true), player(120, false), player(100, true)];
+        assert_eq!(build_pots(&players), vec![
+            Pot { amount: 250, eligible_players: vec![0, 1, 2, 4] }, // 50 de chacun des cinq joueurs
+            Pot { amount: 200, eligible_players: vec![1, 2, 4] }, // 50 de plus jusqu'au tapis de 100
+            Pot { amount: 220, eligible_players: vec![1, 2] }, // Le reste, dont les 20 du joueur couché au-delà de 100
+        ]);
+    }
+
+    #[test]
+    fn uncalled_part_of_the_last_bet_goes_back_to_the_bettor() {
+        let mut players = [player(60, false), player(250, true), player(100, true)];
+        players[0].current_bet = 40; // Couché après avoir suivi 40 dans la rue
+        players[1].current_bet = 200;
+        players[2].current_bet = 50; // Tapis de 50 seulement
+        assert_eq!(return_uncalled_bet(&mut players), Some((1, 150)));
+        assert_eq!((players[1].chips, players[1].current_bet, players[1].total_bet), (150, 50, 100));
+        assert_eq!(build_pots(&players), vec![Pot { amount: 260, eligible_players: vec![1, 2] }]);
+        assert_eq!(return_uncalled_bet(&mut players), None);
+    }
+}
@@ -0,0 +1,229 @@
+use crate::betting::Action;
+use crate::card::Card;
+use crate::hand::{evaluate_hand, HandCategory};
+use rand::{Rng, RngCore};
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum Difficulty { // Niveaux de difficulté des IA, du plus faible au plus fort
+    #[default]
+    Easy,
+    Intermediate,
+    Hard,
+    Expert,
+}
+
+impl Difficulty {
+    pub fn from_level(level: u32) -> Option<Self> { // Niveau saisi par l'utilisateur (1 à 4)
+        match level {
+            1 => Some(Difficulty::Easy),
+            2 => Some(Difficulty::Intermediate),
+            3 => Some(Difficulty::Hard),
+            4 => Some(Difficulty::Expert),
+            _ => None,
+        }
+    }
+
+    pub fn strategy(self) -> &'static dyn Strategy { // Stratégie associée au niveau
+        match self {
+            Difficulty::Easy => &EasyStrategy,
+            Difficulty::Intermediate => &IntermediateStrategy,
+            Difficulty::Hard => &HardStrategy,
+            Difficulty::Expert => &ExpertStrategy,
+        }
+    }
+}
+
+impl fmt::Display for Difficulty {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Difficulty::Easy => "Facile",
+            Difficulty::Intermediate => "Intermédiaire",
+            Difficulty::Hard => "Difficile",
+            Difficulty::Expert => "Extrêmement Difficile",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Position { // Position à la table par rapport au bouton
+    Early,
+    Middle,
+    Late,
+}
+
+impl Position {
+    pub fn from_seat(player_index: usize, dealer_position: usize, num_players: usize) -> Self { // Le bouton et les sièges qui le précèdent parlent en dernier
+        let offset = (player_index + num_players - dealer_position - 1) % num_players; // 0 = premier à parler après le flop
+        if offset < num_players / 3 {
+            Position::Early
+        } else if offset < 2 * num_players / 3 {
+            Position::Middle
+        } else {
+            Position::Late
+        }
+    }
+}
+
+pub struct DecisionContext<'a> { // Tout ce qu'une IA voit au moment d'agir
+    pub hand: &'a [Card],
+    pub community: &'a [Card],
+    pub chips: u32,
+    pub street_bet: u32, // Mise déjà placée dans la rue
+    pub to_call: u32,
+    pub pot: u32,
+    pub big_blind: u32,
+    pub equity: f64, // Part du pot attendue contre les adversaires restants (Monte Carlo)
+    pub position: Position,
+    pub opponents: usize,
+    pub bubble_factor: f64, // Pression ICM près de la bulle: au-delà de 1, perdre des jetons coûte plus que n'en rapporte en gagner
+}
+
+impl DecisionContext<'_> {
+    fn required_equity(&self) -> f64 { // Équité minimale pour que suivre soit rentable d'après les pot odds, corrigée par la pression ICM
+        if self.to_call == 0 {
+            return 0.0;
+        }
+        let risk = self.to_call as f64 * self.bubble_factor;
+        risk / (self.pot as f64 + risk)
+    }
+
+    fn fair_share(&self) -> f64 { // Équité d'une main moyenne face à ce nombre d'adversaires
+        1.0 / (self.opponents + 1) as f64
+    }
+
+    fn call(&self) -> Action {
+        if self.to_call == 0 { Action::Check } else { Action::Call }
+    }
+
+    fn raise(&self, pot_fraction: f64) -> Action { // Suit puis relance d'une fraction du pot (au moins une grosse blind)
+        let raise = ((self.pot as f64 * pot_fraction) as u32).max(self.big_blind);
+        if self.to_call + raise >= self.chips {
+            Action::AllIn
+        } else if self.to_call == 0 && self.street_bet == 0 {
+            Action::Bet(raise)
+        } else {
+            Action::Raise(self.street_bet + self.to_call + raise)
+        }
+    }
+
+    fn check_or_fold(&self) -> Action {
+        if self.to_call == 0 { Action::Check } else { Action::Fold }
+    }
+}
+
+pub trait Strategy { // Décision d'une IA, ramenée ensuite à l'action légale la plus proche
+    fn decide(&self, context: &DecisionContext, rng: &mut dyn RngCore) -> Action;
+}
+
+pub struct EasyStrategy; // Joueur passif: évalue sa main sommairement, suit presque tout, relance rarement
+
+impl Strategy for EasyStrategy {
+    fn decide(&self, context: &DecisionContext, rng: &mut dyn RngCore) -> Action {
+        let strength = hand_strength(context.hand, context.community);
+        if strength > 0.7 && rng.gen_bool(0.2) {
+            context.raise(0.5)
+        } else if context.to_call <= context.big_blind || strength > 0.2 {
+            context.call()
+        } else {
+            context.check_or_fold()
+        }
+    }
+}
+
+pub struct IntermediateStrategy; // Respecte les pot odds et relance ses bonnes mains
+
+impl Strategy for IntermediateStrategy {
+    fn decide(&self, context: &DecisionContext, rng: &mut dyn RngCore) -> Action {
+        if context.equity > 1.6 * context.fair_share() && rng.gen_bool(0.4) {
+            context.raise(0.5)
+        } else if context.equity >= context.required_equity() {
+            context.call()
+        } else {
+            context.check_or_fold()
+        }
+    }
+}
+
+pub struct HardStrategy; // Tient compte de la position et bluffe parfois en fin de parole
+
+impl Strategy for HardStrategy {
+    fn decide(&self, context: &DecisionContext, rng: &mut dyn RngCore) -> Action {
+        let strength = context.equity + position_bonus(context.position);
+        if strength > 1.5 * context.fair_share() && rng.gen_bool(0.6) {
+            context.raise(0.75)
+        } else if context.to_call == 0 && context.position == Position::Late && rng.gen_bool(0.15) {
+            context.raise(0.5)
+        } else if strength >= context.required_equity() {
+            context.call()
+        } else {
+            context.check_or_fold()
+        }
+    }
+}
+
+pub struct ExpertStrategy; // Mesure son avantage sur une main moyenne et dose ses relances
+
+impl Strategy for ExpertStrategy {
+    fn decide(&self, context: &DecisionContext, rng: &mut dyn RngCore) -> Action {
+        let strength = context.equity + position_bonus(context.position);
+        let edge = strength / context.fair_share(); // Au-dessus de 1: meilleure qu'une main moyenne
+        let bluff_chance = if context.opponents <= 2 && context.position == Position::Late { 0.25 } else { 0.05 };
+        if edge > 1.4 {
+            context.raise(edge / 2.0) // Plus l'avantage est grand, plus la relance est grosse
+        } else if context.to_call == 0 && rng.gen_bool(bluff_chance) {
+            context.raise(0.6)
+        } else if strength >= context.required_equity() * 1.1 {
+            context.call()
+        } else {
+            context.check_or_fold()
+        }
+    }
+}
+
+fn position_bonus(position: Position) -> f64 {
+    match position {
+        Position::Early => -0.05,
+        Position::Middle => 0.0,
+        Position::Late => 0.05,
+    }
+}
+
+pub fn hand_strength(hand: &[Card], community: &[Card]) -> f64 { // Estimation rapide de la force d'une main, entre 0 et 1
+    if community.is_empty() {
+        return preflop_strength(hand);
+    }
+    let rank = evaluate_hand(hand, community);
+    let top = rank.kickers[0] as f64 / 14.0;
+    match rank.category {
+        HandCategory::HighCard => 0.05 + top * 0.15,
+        HandCategory::OnePair => 0.3 + top * 0.25,
+        HandCategory::TwoPair => 0.6 + top * 0.1,
+        HandCategory::ThreeOfAKind => 0.75 + top * 0.05,
+        HandCategory::Straight => 0.82,
+        HandCategory::Flush => 0.86,
+        HandCategory::FullHouse => 0.92,
+        HandCategory::FourOfAKind => 0.97,
+        HandCategory::StraightFlush => 1.0,
+    }
+}
+
+fn preflop_strength(hand: &[Card]) -> f64 { // Force des deux cartes privatives: paires, hauteur, couleur et connecteurs
+    if hand.len() < 2 {
+        return 0.0;
+    }
+    let high = hand[0].rank_value().max(hand[1].rank_value()) as f64;
+    let low = hand[0].rank_value().min(hand[1].rank_value()) as f64;
+    if high == low {
+        return 0.5 + high / 14.0 * 0.4;
+    }
+    let mut strength = (high + low) / 28.0 * 0.55;
+    if hand[0].suit == hand[1].suit {
+        strength += 0.05;
+    }
+    if high - low <= 2.0 {
+        strength += 0.03;
+    }
+    strength
+}
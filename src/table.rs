@@ -0,0 +1,115 @@
+use crate::betting::BettingStructure;
+use crate::cash_game::GameMode;
+use std::fmt;
+
+pub const DEFAULT_STARTING_STACK: u32 = 1000; // Cent grosses blinds avec les blinds par défaut
+pub const MAX_SEATS: usize = 10; // Plus grande table acceptée
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GameVariant { // Variante de poker jouée à la table
+    #[default]
+    TexasHoldem,
+}
+
+impl fmt::Display for GameVariant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GameVariant::TexasHoldem => write!(f, "Texas Hold'em"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TableConfig { // Réglages d'une table, vérifiés à la création de la partie
+    pub starting_stack: u32, // Tapis de chaque joueur au départ (cave initiale en partie cash)
+    pub small_blind: u32,
+    pub big_blind: u32,
+    pub ante: u32,
+    pub big_blind_ante: bool,
+    pub straddle: bool,
+    pub max_seats: usize,
+    pub betting_structure: BettingStructure,
+    pub variant: GameVariant,
+    pub mode: GameMode,
+}
+
+impl Default for TableConfig {
+    fn default() -> Self {
+        Self {
+            starting_stack: DEFAULT_STARTING_STACK,
+            small_blind: 5,
+            big_blind: 10,
+            ante: 0,
+            big_blind_ante: false,
+            straddle: false,
+            max_seats: 9,
+            betting_structure: BettingStructure::NoLimit,
+            variant: GameVariant::TexasHoldem,
+            mode: GameMode::Tournament,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigError {
+    NoBigBlind,
+    SmallBlindAboveBigBlind { small_blind: u32, big_blind: u32 },
+    AnteAboveBigBlind { ante: u32, big_blind: u32 },
+    StackBelowBigBlind { starting_stack: u32, big_blind: u32 },
+    Seats(usize),
+    TooManyPlayers { players: usize, max_seats: usize },
+    NotEnoughPlayers(usize),
+    FixedLimitBets,
+    MaxBuyInBelowStack { max_buy_in: u32, starting_stack: u32 },
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::NoBigBlind => write!(f, "la grosse blind doit être positive"),
+            ConfigError::SmallBlindAboveBigBlind { small_blind, big_blind } => write!(f, "la petite blind ({}) dépasse la grosse blind ({})", small_blind, big_blind),
+            ConfigError::AnteAboveBigBlind { ante, big_blind } => write!(f, "l'ante ({}) dépasse la grosse blind ({})", ante, big_blind),
+            ConfigError::StackBelowBigBlind { starting_stack, big_blind } => write!(f, "le tapis de départ ({}) ne couvre pas la grosse blind ({})", starting_stack, big_blind),
+            ConfigError::Seats(seats) => write!(f, "une table compte de 2 à {} sièges, pas {}", MAX_SEATS, seats),
+            ConfigError::TooManyPlayers { players, max_seats } => write!(f, "{} joueurs pour {} sièges", players, max_seats),
+            ConfigError::NotEnoughPlayers(players) => write!(f, "il faut au moins deux joueurs pour un tournoi ({} inscrit)", players),
+            ConfigError::FixedLimitBets => write!(f, "en limite fixe, la petite mise doit être positive, la grosse mise au moins égale, et au moins une mise permise par rue"),
+            ConfigError::MaxBuyInBelowStack { max_buy_in, starting_stack } => write!(f, "la cave maximale ({}) est inférieure au tapis de départ ({})", max_buy_in, starting_stack),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+impl TableConfig {
+    pub fn validate(&self, players: usize) -> Result<(), ConfigError> { // Refuse les combinaisons incohérentes avant de créer la partie
+        if self.big_blind == 0 {
+            return Err(ConfigError::NoBigBlind);
+        }
+        if self.small_blind > self.big_blind {
+            return Err(ConfigError::SmallBlindAboveBigBlind { small_blind: self.small_blind, big_blind: self.big_blind });
+        }
+        if self.ante > self.big_blind {
+            return Err(ConfigError::AnteAboveBigBlind { ante: self.ante, big_blind: self.big_blind });
+        }
+        if self.starting_stack < self.big_blind {
+            return Err(ConfigError::StackBelowBigBlind { starting_stack: self.starting_stack, big_blind: self.big_blind });
+        }
+        if !(2..=MAX_SEATS).contains(&self.max_seats) {
+            return Err(ConfigError::Seats(self.max_seats));
+        }
+        if players > self.max_seats {
+            return Err(ConfigError::TooManyPlayers { players, max_seats: self.max_seats });
+        }
+        if let BettingStructure::FixedLimit { small_bet, big_bet, raise_cap } = self.betting_structure {
+            if small_bet == 0 || big_bet < small_bet || raise_cap == 0 {
+                return Err(ConfigError::FixedLimitBets);
+            }
+        }
+        match self.mode {
+            GameMode::Tournament if players < 2 => Err(ConfigError::NotEnoughPlayers(players)),
+            GameMode::Cash { max_buy_in } if max_buy_in < self.starting_stack => Err(ConfigError::MaxBuyInBelowStack { max_buy_in, starting_stack: self.starting_stack }),
+            _ => Ok(()),
+        }
+    }
+}
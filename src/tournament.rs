@@ -0,0 +1,137 @@
+use std::cmp::Reverse;
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PayoutTable { // Pourcentages de la dotation versés à chaque place, du premier au dernier payé
+    percentages: Vec<u32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PayoutError {
+    Invalid(String),
+    Total(u32),
+}
+
+impl fmt::Display for PayoutError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PayoutError::Invalid(part) => write!(f, "pourcentage invalide: '{}' (attendu par exemple 50/30/20)", part),
+            PayoutError::Total(total) => write!(f, "les pourcentages totalisent {}% au lieu de 100%", total),
+        }
+    }
+}
+
+impl std::error::Error for PayoutError {}
+
+impl PayoutTable {
+    pub fn new(percentages: Vec<u32>) -> Result<Self, PayoutError> {
+        let total = percentages.iter().sum();
+        if total != 100 {
+            return Err(PayoutError::Total(total));
+        }
+        Ok(Self { percentages })
+    }
+
+    pub fn prizes(&self, prize_pool: u32, entrants: usize) -> Vec<u32> { // Gains par place; avec moins de joueurs que de places payées, les pourcentages restants sont répartis
+        let paid = &self.percentages[..self.percentages.len().min(entrants)];
+        let total: u32 = paid.iter().sum();
+        if total == 0 {
+            return vec![0; paid.len()];
+        }
+        let mut prizes: Vec<u32> = paid.iter().map(|&pct| (prize_pool as u64 * pct as u64 / total as u64) as u32).collect();
+        let remainder = prize_pool - prizes.iter().sum::<u32>();
+        prizes[0] += remainder; // Les arrondis reviennent au vainqueur
+        prizes
+    }
+
+    pub fn shared_prizes(&self, first_place: usize, tied: usize, prize_pool: u32, entrants: usize) -> Vec<u32> { // Gains des joueurs à égalité: les places couvertes sont partagées, les unités restantes vont aux premiers
+        let prizes = self.prizes(prize_pool, entrants);
+        let total: u32 = (first_place..first_place + tied).filter_map(|place| prizes.get(place - 1)).sum();
+        let share = total / tied as u32;
+        let remainder = (total % tied as u32) as usize;
+        (0..tied).map(|i| share + u32::from(i < remainder)).collect()
+    }
+}
+
+impl Default for PayoutTable {
+    fn default() -> Self {
+        Self { percentages: vec![50, 30, 20] }
+    }
+}
+
+impl fmt::Display for PayoutTable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.percentages.iter().map(|pct| pct.to_string()).collect::<Vec<_>>().join("/"))
+    }
+}
+
+impl FromStr for PayoutTable { // Notation "50/30/20"
+    type Err = PayoutError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let percentages = s.split('/')
+            .map(|part| part.trim().trim_end_matches('%').parse::<u32>().map_err(|_| PayoutError::Invalid(part.trim().to_string())))
+            .collect::<Result<Vec<u32>, _>>()?;
+        Self::new(percentages)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Finish { // Place finale d'un joueur et ses gains
+    pub place: usize,
+    pub name: String,
+    pub prize: u32,
+}
+
+pub fn rank_eliminations(busted: &[(String, u32)], remaining: usize, payouts: &PayoutTable, prize_pool: u32, entrants: usize) -> Vec<Finish> { // Classe les joueurs éliminés dans la même main: le plus gros tapis de départ finit devant, les tapis égaux partagent la place
+    let mut busted = busted.to_vec();
+    busted.sort_by_key(|(_, stack)| Reverse(*stack));
+    let mut finishes = Vec::with_capacity(busted.len());
+    let mut ahead = 0;
+    for group in busted.chunk_by(|a, b| a.1 == b.1) {
+        let place = remaining + ahead + 1;
+        let prizes = payouts.shared_prizes(place, group.len(), prize_pool, entrants);
+        for ((name, _), prize) in group.iter().zip(prizes) {
+            finishes.push(Finish { place, name: name.clone(), prize });
+        }
+        ahead += group.len();
+    }
+    finishes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn finish(place: usize, name: &str, prize: u32) -> Finish {
+        Finish { place, name: name.to_string(), prize }
+    }
+
+    fn busted(players: &[(&str, u32)]) -> Vec<(String, u32)> {
+        players.iter().map(|&(name, stack)| (name.to_string(), stack)).collect()
+    }
+
+    #[test]
+    fn rounding_remainder_goes_to_first_place() {
+        let payouts = PayoutTable::default();
+        assert_eq!(payouts.prizes(1000, 9), [500, 300, 200]);
+        assert_eq!(payouts.prizes(999, 9), [501, 299, 199]);
+        assert_eq!(payouts.prizes(1000, 2), [625, 375]); // Deux joueurs seulement: 50 et 30 rapportés à 80
+    }
+
+    #[test]
+    fn same_hand_busts_are_ranked_by_starting_stack() {
+        let finishes = rank_eliminations(&busted(&[("Alice", 300), ("Bob", 800), ("Carole", 500)]), 2, &PayoutTable::default(), 1000, 9);
+        assert_eq!(finishes, [finish(3, "Bob", 200), finish(4, "Carole", 0), finish(5, "Alice", 0)]);
+    }
+
+    #[test]
+    fn equal_stacks_share_the_places_they_cover() {
+        let finishes = rank_eliminations(&busted(&[("Alice", 500), ("Bob", 500)]), 1, &PayoutTable::default(), 1000, 9);
+        assert_eq!(finishes, [finish(2, "Alice", 250), finish(2, "Bob", 250)]); // 2e et 3e places: (300 + 200) / 2
+        let finishes = rank_eliminations(&busted(&[("Alice", 400), ("Bob", 400)]), 2, &PayoutTable::default(), 1000, 9);
+        assert_eq!(finishes, [finish(3, "Alice", 100), finish(3, "Bob", 100)]); // 3e place payée, 4e non
+        assert_eq!(PayoutTable::default().shared_prizes(1, 3, 1000, 9), [334, 333, 333]); // L'unité restante va au premier
+    }
+}